@@ -0,0 +1,580 @@
+//! Invariante A.74: Embedded Analytics-Ingestor (INVARIANTS.md A.74, TARGET_ARCHITECTURE §6)
+//!
+//! Blackbox gegen `ironcrab::storage::analytics`:
+//! - Liest rotierte und aktive JSONL-Dateien (inkl. Spill-Dateien A.73) aus dem Log-Root und laedt sie
+//!   in eine eingebettete SQLite-Datei (kein externer Dienst) mit normalisierten Tabellen
+//!   `intents`, `decisions`, `decision_checks`, `executions`, `fills`.
+//! - Idempotent ueber persistierte Datei-Offsets: erneuter Lauf laedt nichts doppelt, halbe Zeilen
+//!   am Dateiende werden erst nach dem abschliessenden `\n` gelesen.
+//! - Von der Retention (A.68) komprimierte `*.jsonl.zst` werden dekomprimiert gelesen; der Offset
+//!   gilt fuer den unkomprimierten Inhalt und setzt nach der Kompression fort.
+//! - Mitgelieferte Queries: PnL pro Source, Reject-Reason-Breakdown, Latenz-Perzentile.
+//! - Laeuft als eigener Prozess `analytics-ingestor`, nie im Hot Path (Source-Contract).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Log-Root wird nur gelesen.
+
+use ironcrab::ipc::{
+    CheckResult, DecisionRecord, ExecutionFees, ExecutionPnl, ExecutionResult, ExplicitAmount,
+    IntentOrigin, IntentTier, TradeIntent, TradeResources, TradeSide, TradingRegime,
+};
+use ironcrab::storage::analytics::{
+    AnalyticsStore, IngestIssue, LatencyPercentiles, LatencyStage, RejectReasonCount, SourcePnl,
+    TableCounts, ANALYTICS_TABLES, CANNED_QUERIES,
+};
+use ironcrab::storage::retention::{apply_plan, plan_stream_dir, RetentionPolicy};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+/// 2025-01-01T00:00:00Z
+const DAY1_MS: u64 = 1_735_689_600_000;
+const MOMENTUM: &str = "momentum-bot";
+const ARB: &str = "arb-strategy";
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+fn intent(id: &str, source: &str, ts_unix_ms: u64) -> TradeIntent {
+    let mut intent = TradeIntent::new(
+        source,
+        "eval-a74",
+        "run-a74",
+        id.to_string(),
+        source,
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(50_000_000, 9),
+        TradeResources {
+            input_mint: "So11111111111111111111111111111111111111112".to_string(),
+            output_mint: "MintA74".to_string(),
+            pools: vec!["PoolA74".to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    );
+    intent.header.ts_unix_ms = ts_unix_ms;
+    intent
+}
+
+fn check(name: &str, passed: bool, reason: Option<&str>) -> CheckResult {
+    CheckResult {
+        check_name: name.to_string(),
+        passed,
+        reason_code: reason.map(str::to_string),
+        details: None,
+    }
+}
+
+fn rejected(id: &str, intent_id: &str, reason: &str, ts_unix_ms: u64) -> DecisionRecord {
+    let mut record = DecisionRecord::new_rejected(
+        "execution-engine",
+        "eval-a74",
+        "run-a74",
+        id.to_string(),
+        intent_id.to_string(),
+        MOMENTUM.to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![
+            check("kill_switch", true, None),
+            check("risk", false, Some(reason)),
+        ],
+        reason.to_string(),
+    );
+    record.header.ts_unix_ms = ts_unix_ms;
+    record
+}
+
+fn confirmed(
+    id: &str,
+    intent_id: &str,
+    source: &str,
+    net_lamports: i64,
+    ts_unix_ms: u64,
+) -> ExecutionResult {
+    let mut r = ExecutionResult::new_sent(
+        "execution-engine",
+        "eval-a74",
+        "run-a74",
+        id.to_string(),
+        format!("dec-{id}"),
+        intent_id.to_string(),
+        source.to_string(),
+        Some("MintA74".to_string()),
+        Some(format!("sig-{id}")),
+        None,
+    )
+    .mark_confirmed(
+        1_000,
+        ExecutionFees {
+            network_fee_lamports: 5_000,
+            tip_lamports: 10_000,
+            compute_units: 120_000,
+        },
+        ExecutionPnl {
+            gross_lamports: net_lamports + 15_000,
+            net_lamports,
+            decimals: 9,
+        },
+        250,
+    );
+    r.header.ts_unix_ms = ts_unix_ms;
+    r
+}
+
+fn with_fill(r: ExecutionResult) -> ExecutionResult {
+    r.with_fills(
+        Some(ExplicitAmount::new(50_000_000, 9)),
+        Some(ExplicitAmount::new(1_234_567, 6)),
+    )
+    .with_side(TradeSide::Buy)
+}
+
+fn append_jsonl<T: Serialize>(path: &Path, records: &[T]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut f = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    for r in records {
+        writeln!(f, "{}", serde_json::to_string(r).unwrap()).unwrap();
+    }
+}
+
+fn day_file(root: &Path, dir: &str, stem: &str, day: u64) -> PathBuf {
+    let date = ["20250101", "20250102"][day as usize];
+    root.join(dir).join(format!("{stem}-{date}.jsonl"))
+}
+
+/// Log-Root fuer die Query-Tests.
+///
+/// - 10 ausgefuehrte Intents (5 momentum, 5 arb), Latenz Intent → Execution 10..100 ms.
+/// - 6 abgelehnte momentum-Intents mit je 2 Checks (1x pass, 1x fail), Latenz Intent → Decision 1..6 ms.
+/// - arb-Executions liegen in der rotierten Folgedatei (Tag 2), eine momentum-Execution nur in der
+///   Spill-Datei, eine weitere in Primaer- und Spill-Datei (Duplikat).
+fn seed_log_root(root: &Path) {
+    let t0 = DAY1_MS + 3_600_000;
+    let momentum_pnl = [100_000, -20_000, 50_000, -5_000, 25_000];
+    let mut intents = Vec::new();
+    let mut primary = Vec::new();
+    for (i, pnl) in momentum_pnl.iter().enumerate() {
+        let ts = t0 + i as u64 * 1_000;
+        let id = format!("i-m-{i:02}");
+        intents.push(intent(&id, MOMENTUM, ts));
+        let exec = with_fill(confirmed(
+            &format!("e-m-{i:02}"),
+            &id,
+            MOMENTUM,
+            *pnl,
+            ts + (i as u64 + 1) * 10,
+        ));
+        match i {
+            3 => append_jsonl(
+                &day_file(root, "executions", "execution_results_spill", 0),
+                &[exec],
+            ),
+            4 => {
+                append_jsonl(
+                    &day_file(root, "executions", "execution_results_spill", 0),
+                    &[&exec],
+                );
+                primary.push(exec);
+            }
+            _ => primary.push(exec),
+        }
+    }
+    append_jsonl(
+        &day_file(root, "executions", "execution_results", 0),
+        &primary,
+    );
+
+    let mut arb_execs = Vec::new();
+    for i in 0..5u64 {
+        let ts = t0 + 10_000 + i * 1_000;
+        let id = format!("i-a-{i:02}");
+        intents.push(intent(&id, ARB, ts));
+        arb_execs.push(confirmed(
+            &format!("e-a-{i:02}"),
+            &id,
+            ARB,
+            10_000,
+            ts + (i + 6) * 10,
+        ));
+    }
+    append_jsonl(
+        &day_file(root, "executions", "execution_results", 1),
+        &arb_execs,
+    );
+
+    let reasons = [
+        "RISK_MAX_SLIPPAGE",
+        "RISK_MAX_POSITION",
+        "RISK_MAX_SLIPPAGE",
+        "TOKEN_UNSAFE",
+        "RISK_MAX_POSITION",
+        "RISK_MAX_SLIPPAGE",
+    ];
+    let mut decisions = Vec::new();
+    for (i, reason) in reasons.iter().enumerate() {
+        let ts = t0 + 20_000 + i as u64 * 1_000;
+        let id = format!("i-r-{i:02}");
+        intents.push(intent(&id, MOMENTUM, ts));
+        decisions.push(rejected(
+            &format!("d-r-{i:02}"),
+            &id,
+            reason,
+            ts + i as u64 + 1,
+        ));
+    }
+    append_jsonl(&day_file(root, "intents", "trade_intents", 0), &intents);
+    append_jsonl(
+        &day_file(root, "decisions", "decision_records", 0),
+        &decisions,
+    );
+}
+
+fn seeded_store() -> (tempfile::TempDir, AnalyticsStore) {
+    let dir = tempdir().unwrap();
+    let logs = dir.path().join("trade_logs");
+    seed_log_root(&logs);
+    let mut store = AnalyticsStore::open(&dir.path().join("analytics.sqlite")).unwrap();
+    let report = store.ingest(&logs).unwrap();
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    (dir, store)
+}
+
+fn counts(intents: u64, decisions: u64, checks: u64, executions: u64, fills: u64) -> TableCounts {
+    TableCounts {
+        intents,
+        decisions,
+        decision_checks: checks,
+        executions,
+        fills,
+    }
+}
+
+/// Eingebettete SQLite-Datei mit fest benannten, normalisierten Tabellen und mitgelieferten Queries.
+#[test]
+fn a74_store_is_embedded_sqlite_file_with_normalized_tables() {
+    assert_eq!(
+        ANALYTICS_TABLES,
+        [
+            "intents",
+            "decisions",
+            "decision_checks",
+            "executions",
+            "fills"
+        ]
+    );
+    assert_eq!(
+        CANNED_QUERIES,
+        ["pnl_by_source", "reject_reasons", "latency_percentiles"]
+    );
+
+    let (dir, store) = seeded_store();
+    let db = dir.path().join("analytics.sqlite");
+    let bytes = fs::read(&db).unwrap();
+    assert!(
+        bytes.starts_with(SQLITE_MAGIC),
+        "Analytics-Store ist eine einzelne SQLite-Datei (kein externer Dienst)"
+    );
+    assert_eq!(store.table_counts().unwrap(), counts(16, 6, 12, 10, 5));
+}
+
+/// Rotierte Folgedateien und Spill-Dateien werden gelesen; Duplikate (gleiche ID) zaehlen einmal.
+#[test]
+fn a74_ingest_reads_rotated_and_spill_files_without_duplicates() {
+    let dir = tempdir().unwrap();
+    let logs = dir.path().join("trade_logs");
+    seed_log_root(&logs);
+    let mut store = AnalyticsStore::open(&dir.path().join("analytics.sqlite")).unwrap();
+
+    let report = store.ingest(&logs).unwrap();
+    assert_eq!(
+        report.files_scanned, 5,
+        "3 Primaer-Dateien Tag 1, 1 rotierte, 1 Spill"
+    );
+    assert_eq!(report.new_records, counts(16, 6, 12, 10, 5));
+    assert_eq!(
+        report.duplicates, 1,
+        "e-m-04 steht in Primaer- und Spill-Datei"
+    );
+}
+
+/// Idempotenz ueber Datei-Offsets: kein Doppel-Load, halbe Zeilen warten auf `\n`, Offsets ueberleben Neustart.
+#[test]
+fn a74_ingest_is_idempotent_via_file_offsets() {
+    let dir = tempdir().unwrap();
+    let logs = dir.path().join("trade_logs");
+    let db = dir.path().join("analytics.sqlite");
+    let active = day_file(&logs, "intents", "trade_intents", 0);
+    append_jsonl(
+        &active,
+        &[
+            intent("i-1", MOMENTUM, DAY1_MS),
+            intent("i-2", MOMENTUM, DAY1_MS + 1),
+        ],
+    );
+
+    let mut store = AnalyticsStore::open(&db).unwrap();
+    assert_eq!(store.ingest(&logs).unwrap().new_records.intents, 2);
+    let again = store.ingest(&logs).unwrap();
+    assert_eq!(again.new_records, TableCounts::default());
+    assert_eq!(again.duplicates, 0, "Offsets verhindern erneutes Lesen");
+
+    // Aktive Datei waechst: eine volle Zeile plus eine halb geschriebene.
+    append_jsonl(&active, &[intent("i-3", MOMENTUM, DAY1_MS + 2)]);
+    let partial = serde_json::to_string(&intent("i-4", MOMENTUM, DAY1_MS + 3)).unwrap();
+    let (head, tail) = partial.split_at(partial.len() / 2);
+    let mut f = fs::OpenOptions::new().append(true).open(&active).unwrap();
+    write!(f, "{head}").unwrap();
+    f.flush().unwrap();
+
+    let grown = store.ingest(&logs).unwrap();
+    assert_eq!(grown.new_records.intents, 1, "nur die vollstaendige Zeile");
+    assert!(grown.issues.is_empty(), "halbe Zeile ist kein Fehler");
+
+    writeln!(f, "{tail}").unwrap();
+    f.flush().unwrap();
+    assert_eq!(store.ingest(&logs).unwrap().new_records.intents, 1);
+    drop(store);
+
+    // Neue Instanz auf derselben DB setzt an den persistierten Offsets fort.
+    let mut reopened = AnalyticsStore::open(&db).unwrap();
+    let report = reopened.ingest(&logs).unwrap();
+    assert_eq!(report.new_records, TableCounts::default());
+    assert_eq!(report.duplicates, 0);
+    assert_eq!(reopened.table_counts().unwrap().intents, 4);
+}
+
+/// Retention (A.68) komprimiert eine teilweise geladene Datei zu `.jsonl.zst` und loescht das
+/// Original: der Ingestor liest die `.zst` dekomprimiert ab dem persistierten Offset weiter.
+#[test]
+fn a74_ingest_continues_from_offset_after_zstd_compression() {
+    let dir = tempdir().unwrap();
+    let logs = dir.path().join("trade_logs");
+    let db = dir.path().join("analytics.sqlite");
+    let closed = day_file(&logs, "intents", "trade_intents", 0);
+    append_jsonl(
+        &closed,
+        &[
+            intent("i-1", MOMENTUM, DAY1_MS),
+            intent("i-2", MOMENTUM, DAY1_MS + 1),
+        ],
+    );
+    let mut store = AnalyticsStore::open(&db).unwrap();
+    assert_eq!(store.ingest(&logs).unwrap().new_records.intents, 2);
+
+    // Nach dem letzten Ingest, vor der Kompression noch geschrieben.
+    append_jsonl(&closed, &[intent("i-3", MOMENTUM, DAY1_MS + 2)]);
+
+    // 2025-01-02T12:00:00Z: Tag 1 ist geschlossen und wird komprimiert.
+    let now_ms = DAY1_MS + 36 * 3_600_000;
+    let policy = RetentionPolicy {
+        max_age_days: 30,
+        max_total_bytes: u64::MAX,
+        compress_closed: true,
+    };
+    let stream_dir = logs.join("intents");
+    let plan = plan_stream_dir(&stream_dir, &policy, now_ms, &HashSet::new()).unwrap();
+    assert_eq!(plan.compress, vec![closed.clone()]);
+    apply_plan(&logs, &plan).unwrap();
+    assert!(!closed.exists());
+    assert!(stream_dir.join("trade_intents-20250101.jsonl.zst").exists());
+
+    let report = store.ingest(&logs).unwrap();
+    assert_eq!(report.files_scanned, 1);
+    assert_eq!(
+        report.new_records.intents, 1,
+        "nur i-3, ab dem Offset im unkomprimierten Inhalt"
+    );
+    assert_eq!(report.duplicates, 0, "i-1/i-2 werden nicht erneut gelesen");
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert_eq!(
+        store.ingest(&logs).unwrap().new_records,
+        TableCounts::default()
+    );
+    assert_eq!(store.table_counts().unwrap().intents, 3);
+
+    // Frische DB: die `.zst` wird vollstaendig gelesen.
+    let mut fresh = AnalyticsStore::open(&dir.path().join("fresh.sqlite")).unwrap();
+    let report = fresh.ingest(&logs).unwrap();
+    assert_eq!(report.new_records.intents, 3);
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+/// Integritaets-Marker (A.72) werden uebersprungen, kaputte Zeilen gemeldet, der Rest geladen.
+#[test]
+fn a74_ingest_skips_integrity_markers_and_reports_bad_lines() {
+    let dir = tempdir().unwrap();
+    let logs = dir.path().join("trade_logs");
+    let path = day_file(&logs, "intents", "trade_intents", 0);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    let with_chain = |id: &str, seq: u64| {
+        let mut v = serde_json::to_value(intent(id, ARB, DAY1_MS + seq)).unwrap();
+        v["_seq"] = seq.into();
+        v["_chain"] = "00".repeat(32).into();
+        serde_json::to_string(&v).unwrap()
+    };
+    let lines = [
+        r#"{"_integrity":"header","stem":"trade_intents","prev_file":null,"prev_chain":null}"#
+            .to_string(),
+        with_chain("i-ok-1", 1),
+        r#"{"_integrity":"gap","dropped":3,"_seq":2,"_chain":"ab"}"#.to_string(),
+        "{\"intent_id\": \"i-broken\"".to_string(),
+        with_chain("i-ok-2", 3),
+        r#"{"_integrity":"footer","records":3,"last_seq":3,"chain":"cd"}"#.to_string(),
+    ];
+    fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+    let mut store = AnalyticsStore::open(&dir.path().join("analytics.sqlite")).unwrap();
+    let report = store.ingest(&logs).unwrap();
+    assert_eq!(report.new_records.intents, 2);
+    assert_eq!(report.skipped_lines, 1);
+    assert_eq!(
+        report.issues,
+        vec![IngestIssue::MalformedLine {
+            file: path,
+            line: 4
+        }]
+    );
+
+    // Die kaputte Zeile wird nicht bei jedem Lauf erneut gemeldet.
+    let again = store.ingest(&logs).unwrap();
+    assert!(again.issues.is_empty());
+    assert_eq!(again.skipped_lines, 0);
+}
+
+/// Canned Query: realisierter Netto-PnL und Fees pro Source, nur bestaetigte Executions.
+#[test]
+fn a74_pnl_by_source() {
+    let (_dir, store) = seeded_store();
+    assert_eq!(
+        store.pnl_by_source().unwrap(),
+        vec![
+            SourcePnl {
+                source: ARB.to_string(),
+                executions: 5,
+                net_pnl_lamports: 50_000,
+                fees_lamports: 75_000,
+            },
+            SourcePnl {
+                source: MOMENTUM.to_string(),
+                executions: 5,
+                net_pnl_lamports: 150_000,
+                fees_lamports: 75_000,
+            },
+        ]
+    );
+}
+
+/// Canned Query: Reject-Gruende nach Haeufigkeit (absteigend), bei Gleichstand alphabetisch.
+#[test]
+fn a74_reject_reason_breakdown() {
+    let (_dir, store) = seeded_store();
+    let reason = |r: &str, count: u64| RejectReasonCount {
+        reason: r.to_string(),
+        count,
+    };
+    assert_eq!(
+        store.reject_reasons().unwrap(),
+        vec![
+            reason("RISK_MAX_SLIPPAGE", 3),
+            reason("RISK_MAX_POSITION", 2),
+            reason("TOKEN_UNSAFE", 1),
+        ]
+    );
+}
+
+/// Canned Query: Latenz-Perzentile (Nearest-Rank) pro Stufe, optional pro Source.
+#[test]
+fn a74_latency_percentiles() {
+    let (_dir, store) = seeded_store();
+    let p = |samples, p50_ms, p90_ms, p99_ms, max_ms| {
+        Some(LatencyPercentiles {
+            samples,
+            p50_ms,
+            p90_ms,
+            p99_ms,
+            max_ms,
+        })
+    };
+
+    assert_eq!(
+        store
+            .latency_percentiles(LatencyStage::IntentToExecution, None)
+            .unwrap(),
+        p(10, 50, 90, 100, 100)
+    );
+    assert_eq!(
+        store
+            .latency_percentiles(LatencyStage::IntentToExecution, Some(ARB))
+            .unwrap(),
+        p(5, 80, 100, 100, 100)
+    );
+    assert_eq!(
+        store
+            .latency_percentiles(LatencyStage::IntentToDecision, None)
+            .unwrap(),
+        p(6, 3, 6, 6, 6)
+    );
+    assert_eq!(
+        store
+            .latency_percentiles(LatencyStage::IntentToDecision, Some("unknown"))
+            .unwrap(),
+        None,
+        "keine Samples → None statt 0"
+    );
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: eigener Prozess `analytics-ingestor`; Hot-Path-Binaries kennen den Store nicht.
+#[test]
+fn a74_ingestor_binary_is_off_hot_path() {
+    let bin = iron_crab_bin_rs("analytics_ingestor");
+    let Ok(src) = fs::read_to_string(&bin) else {
+        eprintln!("SKIP: {bin:?} nicht vorhanden (Geschwister-Layout fehlt)");
+        return;
+    };
+    assert!(
+        src.contains("AnalyticsStore"),
+        "analytics-ingestor muss storage::analytics nutzen"
+    );
+    for needle in ["--log-dir", "--db", "--interval-ms"]
+        .into_iter()
+        .chain(CANNED_QUERIES.iter().copied())
+    {
+        assert!(src.contains(needle), "analytics-ingestor: {needle} fehlt");
+    }
+
+    for hot in [
+        "market_data",
+        "execution_engine",
+        "momentum_bot",
+        "arb_strategy",
+    ] {
+        let Ok(hot_src) = fs::read_to_string(iron_crab_bin_rs(hot)) else {
+            continue;
+        };
+        assert!(
+            !hot_src.contains("storage::analytics") && !hot_src.contains("AnalyticsStore"),
+            "{hot}: Analytics-Store darf nicht im Hot Path laufen"
+        );
+    }
+}
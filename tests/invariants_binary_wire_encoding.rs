@@ -0,0 +1,471 @@
+//! Invariante A.65: Binaeres Wire-Encoding fuer High-Volume-Topics (INVARIANTS.md A.65)
+//!
+//! Blackbox gegen `ironcrab::ipc::wire`:
+//! - Kompaktes Binaerformat (Protobuf, stabiles Schema) neben JSON fuer `ironcrab::ipc`-Typen.
+//! - Aushandlung per `Content-Type`-Header; fehlender Header = JSON (Altbestand, Golden Replays).
+//! - Subscriber akzeptieren beide Formate; Binaer-Roundtrip ist aequivalent zum JSON-Roundtrip
+//!   (gleiche Records wie `ipc_schema_serde.rs`).
+//! - Binaer-Payloads tragen einen Frame (Magic `ICPB`, Frame-Version, Type-ID); das Schema ist
+//!   per `.proto` und Golden-Payload unter `tests/fixtures/wire/v1/` eingefroren.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; JSONL-Dateien bleiben JSON.
+
+use ironcrab::ipc::wire::{
+    decode, encode, wire_headers, WireCodec, WireError, WireFormat, BINARY_FRAME_VERSION,
+    BINARY_MAGIC, CONTENT_TYPE_BINARY, CONTENT_TYPE_HEADER, CONTENT_TYPE_JSON,
+};
+use ironcrab::ipc::{
+    CheckResult, DecisionRecord, ExecutionFees, ExecutionPnl, ExecutionResult, ExecutionStatus,
+    ExplicitAmount, FillStatus, FillUnavailableReason, IntentOrigin, IntentTier, MarketEvent,
+    MarketEventKind, PoolCacheUpdate, PoolCacheUpdateType, RecordHeader, SimulationResult,
+    TradeIntent, TradeResources, TradeSide, TradingRegime,
+};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// 2025-01-01T00:00:00Z
+const FIXED_TS_MS: u64 = 1_735_689_600_000;
+
+fn wire_fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("wire")
+        .join("v1")
+        .join(name)
+}
+
+/// Binaer hin und zurueck; Vergleich ueber die JSON-Darstellung (gleiche Semantik wie
+/// `ipc_schema_serde.rs`, inkl. `Decimal`, `f64`-`ui` und optionaler Felder).
+fn assert_binary_equivalent<T: WireCodec + std::fmt::Debug>(record: &T) {
+    let expected = serde_json::to_value(record).unwrap();
+    let bytes = encode(record, WireFormat::Binary).unwrap();
+    let decoded: T = decode(Some(CONTENT_TYPE_BINARY), &bytes)
+        .unwrap_or_else(|e| panic!("Binaer-Decode fehlgeschlagen: {e:?}"));
+    assert_eq!(
+        serde_json::to_value(&decoded).unwrap(),
+        expected,
+        "Binaer-Roundtrip weicht vom JSON-Modell ab"
+    );
+
+    let json = encode(record, WireFormat::Json).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Value>(&json).unwrap(),
+        expected,
+        "JSON-Wire = serde_json (Golden Replays unveraendert)"
+    );
+}
+
+fn market_event_pool_created() -> MarketEvent {
+    MarketEvent::new(
+        "market-data",
+        "v0.1.0",
+        "run-abc",
+        "evt-001".to_string(),
+        "geyser",
+        Some(12345),
+        MarketEventKind::PoolCreated {
+            pool_address: "Pool123".to_string(),
+            base_mint: "BaseMint".to_string(),
+            quote_mint: "QuoteMint".to_string(),
+            dex: "raydium".to_string(),
+            initial_liquidity_sol: Some(Decimal::from_str("100.123456789").unwrap()),
+        },
+    )
+}
+
+fn market_event_token_mint_info() -> MarketEvent {
+    MarketEvent::new(
+        "market-data",
+        "v0.1.0",
+        "run-abc",
+        "evt-mint-001".to_string(),
+        "geyser",
+        Some(555),
+        MarketEventKind::TokenMintInfo {
+            mint: "So11111111111111111111111111111111111111112".to_string(),
+            token_program: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            decimals: 9,
+            supply: 1_000_000_000,
+            mint_authority: None,
+            freeze_authority: Some("FreezeAuth1111111111111111111111111111111".to_string()),
+            extensions: Vec::new(),
+        },
+    )
+}
+
+fn pool_cache_update() -> PoolCacheUpdate {
+    let mut metadata = HashMap::new();
+    metadata.insert("creator".to_string(), "Creator111".to_string());
+    metadata.insert("cashback_enabled".to_string(), "true".to_string());
+    PoolCacheUpdate {
+        header: RecordHeader::new("market-data", "v0.1", "run-eval-a65"),
+        dex: "pumpfun".to_string(),
+        base_mint: "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string(),
+        quote_mint: "So11111111111111111111111111111111111111112".to_string(),
+        base_reserve: 793_100_000_000_000,
+        quote_reserve: 30_000_000_000,
+        pool_address: "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP".to_string(),
+        metadata: Some(metadata),
+        geyser_slot: 100,
+        liquidity_lamports: Some(30_000_000_000),
+        update_type: PoolCacheUpdateType::PoolDiscovered,
+    }
+}
+
+fn market_event_slot_update() -> MarketEvent {
+    MarketEvent::new(
+        "test",
+        "v1",
+        "run",
+        "e1".to_string(),
+        "test",
+        Some(1),
+        MarketEventKind::SlotUpdate { current_slot: 1 },
+    )
+}
+
+fn trade_intent() -> TradeIntent {
+    let mut resources = TradeResources::default();
+    resources.pools.push("PoolAddr".to_string());
+    resources.input_mint = "So11111111111111111111111111111111111111112".to_string();
+    resources.output_mint = "MintAddr".to_string();
+    TradeIntent::new(
+        "momentum-bot",
+        "v0.1",
+        "run-1",
+        "intent-001".to_string(),
+        "momentum-bot",
+        IntentTier::Tier0,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(10_000_000, 9),
+        resources,
+        0,
+        300,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    )
+}
+
+fn decision_record_sim_failed() -> DecisionRecord {
+    DecisionRecord::new_sim_failed(
+        "execution-engine",
+        "v0.1.0",
+        "run-456",
+        "dec-002".to_string(),
+        "intent-002".to_string(),
+        "arb-strategy".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Established,
+        vec![CheckResult {
+            check_name: "simulation".to_string(),
+            passed: false,
+            reason_code: Some("SIM_FAILED".to_string()),
+            details: Some("InstructionError".to_string()),
+        }],
+        "plan-hash-xyz".to_string(),
+        SimulationResult {
+            success: false,
+            error_code: Some("InstructionError".to_string()),
+            logs_preview: Some("Program failed: insufficient funds".to_string()),
+            compute_units_consumed: Some(50_000),
+        },
+    )
+}
+
+fn decision_record_rejected() -> DecisionRecord {
+    DecisionRecord::new_rejected(
+        "exec-engine",
+        "v0.1",
+        "run-1",
+        "dec-001".to_string(),
+        "intent-001".to_string(),
+        "momentum-bot".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![CheckResult {
+            check_name: "test_check".to_string(),
+            passed: true,
+            reason_code: None,
+            details: None,
+        }],
+        "TEST".to_string(),
+    )
+}
+
+fn execution_result_sent() -> ExecutionResult {
+    ExecutionResult::new_sent(
+        "exec-engine",
+        "v0.1",
+        "run-1",
+        "exec-001".to_string(),
+        "decision-001".to_string(),
+        "intent-001".to_string(),
+        "momentum-bot".to_string(),
+        Some("Mint11111111111111111111111111111111".to_string()),
+        Some("sig123".to_string()),
+        None,
+    )
+}
+
+fn execution_result_error_code() -> ExecutionResult {
+    let mut result = ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-789",
+        "exe-003".to_string(),
+        "dec-003".to_string(),
+        "intent-003".to_string(),
+        "momentum-bot".to_string(),
+        Some("mint123".to_string()),
+        None,
+        None,
+    )
+    .with_error_code(Some("Custom(6005)".to_string()));
+    result.status = ExecutionStatus::Failed;
+    result.error_message = Some("execution_failed".to_string());
+    result
+}
+
+fn execution_result_fill_unavailable() -> ExecutionResult {
+    ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-789",
+        "exe-002".to_string(),
+        "dec-002".to_string(),
+        "intent-002".to_string(),
+        "momentum-bot".to_string(),
+        Some("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string()),
+        Some("5abcdef123456...".to_string()),
+        None,
+    )
+    .mark_confirmed(
+        12345,
+        ExecutionFees {
+            network_fee_lamports: 5000,
+            tip_lamports: 0,
+            compute_units: 150000,
+        },
+        ExecutionPnl {
+            gross_lamports: 0,
+            net_lamports: -5000,
+            decimals: 9,
+        },
+        250,
+    )
+    .with_fills(None, None)
+    .with_fill_diagnostics(
+        FillStatus::Unavailable,
+        Some(FillUnavailableReason::RpcTxFetchFailed),
+    )
+}
+
+/// Content-Type-Konstanten und Aushandlung; fehlender Header = JSON (Altbestand).
+#[test]
+fn a65_content_type_negotiation() {
+    assert_eq!(CONTENT_TYPE_HEADER, "Content-Type");
+    assert_eq!(CONTENT_TYPE_JSON, "application/json");
+    assert_eq!(CONTENT_TYPE_BINARY, "application/x-protobuf");
+    assert_eq!(WireFormat::default(), WireFormat::Json);
+
+    assert_eq!(WireFormat::Json.content_type(), CONTENT_TYPE_JSON);
+    assert_eq!(WireFormat::Binary.content_type(), CONTENT_TYPE_BINARY);
+    assert_eq!(
+        WireFormat::from_content_type(None).unwrap(),
+        WireFormat::Json
+    );
+    assert_eq!(
+        WireFormat::from_content_type(Some(CONTENT_TYPE_BINARY)).unwrap(),
+        WireFormat::Binary
+    );
+    assert!(matches!(
+        WireFormat::from_content_type(Some("application/cbor")),
+        Err(WireError::UnsupportedContentType(_))
+    ));
+
+    let headers = wire_headers(WireFormat::Binary);
+    assert_eq!(
+        headers.get(CONTENT_TYPE_HEADER).map(|v| v.as_str()),
+        Some(CONTENT_TYPE_BINARY)
+    );
+}
+
+/// Subscriber akzeptieren beide Formate: dasselbe Record als JSON (mit und ohne Header) und
+/// binaer liefert dasselbe Ergebnis.
+#[test]
+fn a65_subscriber_accepts_json_and_binary() {
+    let update = pool_cache_update();
+    let json = encode(&update, WireFormat::Json).unwrap();
+    let bin = encode(&update, WireFormat::Binary).unwrap();
+
+    let from_legacy: PoolCacheUpdate = decode(None, &json).unwrap();
+    let from_json: PoolCacheUpdate = decode(Some(CONTENT_TYPE_JSON), &json).unwrap();
+    let from_bin: PoolCacheUpdate = decode(Some(CONTENT_TYPE_BINARY), &bin).unwrap();
+    let expected = serde_json::to_value(&update).unwrap();
+    for decoded in [from_legacy, from_json, from_bin] {
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), expected);
+    }
+}
+
+#[test]
+fn a65_market_event_binary_roundtrip_equivalent() {
+    assert_binary_equivalent(&market_event_pool_created());
+    assert_binary_equivalent(&market_event_token_mint_info());
+    assert_binary_equivalent(&market_event_slot_update());
+}
+
+#[test]
+fn a65_pool_cache_update_binary_roundtrip_equivalent() {
+    assert_binary_equivalent(&pool_cache_update());
+    let mut minimal = pool_cache_update();
+    minimal.metadata = None;
+    minimal.liquidity_lamports = None;
+    assert_binary_equivalent(&minimal);
+}
+
+#[test]
+fn a65_trade_intent_binary_roundtrip_equivalent() {
+    assert_binary_equivalent(&trade_intent());
+
+    let with_min_out: TradeIntent = serde_json::from_str(
+        r#"{
+        "schema_version":1,
+        "ts_unix_ms":1700000000000,
+        "component":"test",
+        "build":"test",
+        "run_id":"run",
+        "intent_id":"intent-typed-1",
+        "source":"test",
+        "tier":"Tier1",
+        "origin_type":"StrategyA",
+        "ttl_ms":5000,
+        "required_capital":{"raw":1,"decimals":9},
+        "resources":{"input_mint":"in","output_mint":"out","pools":["pool"],"accounts":[]},
+        "expected_roi_bps":0,
+        "max_slippage_bps":0,
+        "side":"Sell",
+        "regime":"Early",
+        "execution":{"min_out":{"raw":42,"decimals":9}}
+    }"#,
+    )
+    .unwrap();
+    assert_binary_equivalent(&with_min_out);
+}
+
+#[test]
+fn a65_decision_record_binary_roundtrip_equivalent() {
+    assert_binary_equivalent(&decision_record_sim_failed());
+    assert_binary_equivalent(&decision_record_rejected());
+}
+
+#[test]
+fn a65_execution_result_binary_roundtrip_equivalent() {
+    assert_binary_equivalent(&execution_result_sent());
+    assert_binary_equivalent(&execution_result_error_code());
+    assert_binary_equivalent(&execution_result_fill_unavailable());
+}
+
+/// Stabiles Schema: das Binaer-Encoding ist byte-exakt gleich der eingefrorenen Golden-Payload
+/// (`pool_cache_update.proto`, Frame + Message, Felder in Nummernreihenfolge, Map-Eintraege nach
+/// Key sortiert), und die Golden-Payload dekodiert zum selben Record.
+#[test]
+fn a65_binary_encoding_matches_frozen_golden_payload() {
+    let proto = fs::read_to_string(wire_fixture("pool_cache_update.proto")).unwrap();
+    assert!(proto.contains("package ironcrab.ipc.v1;"));
+    let golden = fs::read(wire_fixture("pool_cache_update.bin")).unwrap();
+    assert_eq!(&golden[..4], &BINARY_MAGIC);
+    assert_eq!(golden[4], BINARY_FRAME_VERSION);
+
+    let mut update = pool_cache_update();
+    update.header.ts_unix_ms = FIXED_TS_MS;
+    assert_eq!(update.header.schema_version, 1, "Golden-Payload ist v1");
+
+    let encoded = encode(&update, WireFormat::Binary).unwrap();
+    assert_eq!(
+        encoded.as_ref(),
+        golden.as_slice(),
+        "Binaer-Encoding weicht vom eingefrorenen Schema ab"
+    );
+    let decoded: PoolCacheUpdate = decode(Some(CONTENT_TYPE_BINARY), &golden).unwrap();
+    assert_eq!(
+        serde_json::to_value(&decoded).unwrap(),
+        serde_json::to_value(&update).unwrap()
+    );
+}
+
+/// Zweck der Aenderung: Binaer ist fuer die High-Volume-Typen deutlich kleiner als JSON.
+#[test]
+fn a65_binary_is_smaller_for_high_volume_types() {
+    for (name, json, bin) in [
+        (
+            "MarketEvent",
+            encode(&market_event_pool_created(), WireFormat::Json).unwrap(),
+            encode(&market_event_pool_created(), WireFormat::Binary).unwrap(),
+        ),
+        (
+            "PoolCacheUpdate",
+            encode(&pool_cache_update(), WireFormat::Json).unwrap(),
+            encode(&pool_cache_update(), WireFormat::Binary).unwrap(),
+        ),
+    ] {
+        assert!(
+            bin.len() * 10 <= json.len() * 7,
+            "{name}: binaer {} B vs JSON {} B (erwartet <= 70 %)",
+            bin.len(),
+            json.len()
+        );
+    }
+}
+
+/// Falscher Header oder kaputte Payload → typisierter Fehler, kein Fallback auf das andere
+/// Format. Die Binaer-Erkennung haengt am Frame, nicht an der Toleranz des Protobuf-Parsers.
+#[test]
+fn a65_mismatched_or_corrupt_payload_is_rejected() {
+    assert_eq!(&BINARY_MAGIC, b"ICPB");
+    assert_eq!(BINARY_FRAME_VERSION, 1);
+
+    let json = encode(&pool_cache_update(), WireFormat::Json).unwrap();
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_BINARY), &json),
+        Err(WireError::BadMagic)
+    ));
+    let bin = encode(&pool_cache_update(), WireFormat::Binary).unwrap();
+    assert_eq!(&bin[..4], &BINARY_MAGIC);
+    assert_eq!(bin[4], BINARY_FRAME_VERSION);
+
+    let mut future = bin.to_vec();
+    future[4] = BINARY_FRAME_VERSION + 1;
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_BINARY), &future),
+        Err(WireError::UnsupportedFrameVersion(v)) if v == BINARY_FRAME_VERSION + 1
+    ));
+
+    let event = encode(&market_event_pool_created(), WireFormat::Binary).unwrap();
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_BINARY), &event),
+        Err(WireError::TypeMismatch { .. })
+    ));
+
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_BINARY), &bin[..3]),
+        Err(WireError::BadMagic)
+    ));
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_BINARY), &bin[..bin.len() - 3]),
+        Err(WireError::Decode { .. })
+    ));
+
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some(CONTENT_TYPE_JSON), &bin),
+        Err(WireError::Decode { .. })
+    ));
+    assert!(matches!(
+        decode::<PoolCacheUpdate>(Some("text/plain"), &json),
+        Err(WireError::UnsupportedContentType(_))
+    ));
+}
@@ -0,0 +1,358 @@
+//! Invariante A.54: Chunked / TWAP Exit fuer grosse Positionen (INVARIANTS.md A.54)
+//!
+//! Blackbox gegen `ironcrab::execution::chunked_exit` + `TradeIntent.execution.exit_mode`:
+//! - Slice-Groesse aus Max-Price-Impact-Ziel, pro Slice neu gequotet.
+//! - Slices mit Mindestabstand in Slots; naechster Slice erst nach Fill des vorherigen.
+//! - Parent/Child-Beziehung in DecisionRecord und ExecutionResult.
+//! - Abort gibt verbleibende Token-Locks frei.
+//!
+//! Quotes kommen als Closure (`amount_in -> Option<amount_out>`), damit derselbe Planer mit
+//! `quote_exact_in` jedes Connectors funktioniert. Hier: CPMM ohne Fee (x*y=k) als Referenz.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests.
+
+use ironcrab::execution::chunked_exit::{
+    price_impact_bps, size_slice, ChunkAction, ChunkedExit, ExitAbortSummary,
+};
+use ironcrab::ipc::{
+    CheckResult, ChunkedExitParams, DecisionRecord, ExecutionResult, ExitMode, IntentOrigin,
+    TradeIntent, TradingRegime,
+};
+use ironcrab::storage::{LockHolder, LockManager, LockResult};
+use std::collections::HashMap;
+
+const MINT: &str = "Mint111111111111111111111111111111111111111";
+const PARENT: &str = "liq-parent-001";
+
+/// CPMM-Sell-Quote: Token (x) → SOL (y), ohne Fee.
+fn cpmm(token_reserve: u64, sol_reserve: u64) -> impl Fn(u64) -> Option<u64> {
+    move |amount_in: u64| {
+        let x = token_reserve as u128;
+        let y = sol_reserve as u128;
+        let a = amount_in as u128;
+        Some((y * a / (x + a)) as u64)
+    }
+}
+
+fn params() -> ChunkedExitParams {
+    ChunkedExitParams {
+        max_price_impact_bps: 100,
+        min_slot_spacing: 4,
+        max_slices: 10,
+        min_slice_raw: 1_000,
+    }
+}
+
+fn locked_manager(total: u64) -> LockManager {
+    let manager = LockManager::new(1_000_000_000).with_fairness(5, 60, 30, false);
+    manager.set_available_token_balance(MINT.to_string(), total);
+    manager
+}
+
+/// Schema: `execution.exit_mode` ist additiv; fehlend = Single (ein Swap wie bisher).
+#[test]
+fn a54_exit_mode_schema_roundtrip_and_default() {
+    let json = r#"{
+        "schema_version":1,
+        "ts_unix_ms":1700000000000,
+        "component":"execution-engine",
+        "build":"test",
+        "run_id":"run",
+        "intent_id":"liq-parent-001",
+        "source":"execution-engine",
+        "tier":"Tier1",
+        "origin_type":"StrategyA",
+        "ttl_ms":5000,
+        "required_capital":{"raw":0,"decimals":9},
+        "resources":{"input_mint":"Mint111111111111111111111111111111111111111","output_mint":"So11111111111111111111111111111111111111112","pools":["pool"],"accounts":[]},
+        "expected_roi_bps":0,
+        "max_slippage_bps":500,
+        "side":"Sell",
+        "regime":"Established",
+        "execution":{"exit_mode":{"mode":"chunked","max_price_impact_bps":100,"min_slot_spacing":4,"max_slices":10,"min_slice_raw":1000}}
+    }"#;
+    let parsed: TradeIntent = serde_json::from_str(json).expect("chunked exit intent");
+    let mode = parsed
+        .execution
+        .as_ref()
+        .and_then(|e| e.exit_mode.clone())
+        .expect("exit_mode parsed");
+    assert_eq!(mode, ExitMode::Chunked(params()));
+
+    let back: TradeIntent = serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+    assert_eq!(
+        back.execution.as_ref().and_then(|e| e.exit_mode.clone()),
+        Some(ExitMode::Chunked(params()))
+    );
+
+    let legacy = json.replace(
+        r#""execution":{"exit_mode":{"mode":"chunked","max_price_impact_bps":100,"min_slot_spacing":4,"max_slices":10,"min_slice_raw":1000}}"#,
+        r#""execution":{"min_out":{"raw":42,"decimals":9}}"#,
+    );
+    let legacy: TradeIntent = serde_json::from_str(&legacy).unwrap();
+    assert_eq!(
+        legacy
+            .execution
+            .as_ref()
+            .and_then(|e| e.exit_mode.clone())
+            .unwrap_or_default(),
+        ExitMode::Single
+    );
+}
+
+/// Slice ist eine Grenze der Spec-Formel: Impact(slice) <= Ziel, Impact(slice + 1) > Ziel.
+#[test]
+fn a54_slice_size_respects_price_impact_target() {
+    let quote = cpmm(1_000_000_000, 10_000_000_000);
+    let slice = size_slice(500_000_000, 100, 1_000, &quote).expect("slice");
+
+    assert!(
+        price_impact_bps(slice, 1_000, &quote).unwrap() <= 100,
+        "Slice darf Impact-Ziel nicht ueberschreiten"
+    );
+    assert!(
+        price_impact_bps(slice + 1, 1_000, &quote).unwrap() > 100,
+        "Slice muss maximal sein"
+    );
+    // Spec-Formel mit ganzzahligen CPMM-Quotes (out(1000) = 9_999): Impact 100 bps bei 10_304_075,
+    // 101 bei +1..+4, wieder 100 bei 10_304_080 (Rundung), ab 10_304_081 dauerhaft > 100.
+    assert!((10_304_075..=10_304_080).contains(&slice), "slice={slice}");
+}
+
+/// Rest passt unter das Impact-Ziel → ein Slice mit dem ganzen Rest.
+#[test]
+fn a54_small_remaining_is_single_slice() {
+    let quote = cpmm(1_000_000_000, 10_000_000_000);
+    assert_eq!(size_slice(5_000_000, 100, 1_000, &quote), Some(5_000_000));
+}
+
+/// Kein Quote → kein Slice (kein Blind-Sell ohne Quote).
+#[test]
+fn a54_no_quote_no_slice() {
+    let no_quote = |_: u64| None;
+    assert_eq!(size_slice(5_000_000, 100, 1_000, &no_quote), None);
+}
+
+/// Ablauf: Submit → AwaitingFill → WaitUntil(spacing) → Submit (neu gequotet) → … → Complete.
+#[test]
+fn a54_slices_are_spaced_by_slots_and_requoted() {
+    let total = 30_000_000;
+    let manager = locked_manager(total);
+    let mut exit =
+        ChunkedExit::start(PARENT, MINT, total, params(), &manager).expect("locks acquired");
+
+    let deep = cpmm(1_000_000_000, 10_000_000_000);
+    let first = match exit.next_action(100, &deep) {
+        ChunkAction::Submit {
+            slice_index,
+            child_intent_id,
+            amount_raw,
+        } => {
+            assert_eq!(slice_index, 0);
+            assert_eq!(child_intent_id, format!("{PARENT}#slice-0"));
+            assert!(amount_raw < total);
+            amount_raw
+        }
+        other => panic!("expected Submit, got {other:?}"),
+    };
+    assert_eq!(
+        exit.next_action(101, &deep),
+        ChunkAction::AwaitingFill { slice_index: 0 },
+        "kein zweiter Slice vor Fill des ersten"
+    );
+
+    exit.record_slice_fill(0, first);
+    assert_eq!(exit.remaining_raw(), total - first);
+    assert_eq!(
+        exit.next_action(102, &deep),
+        ChunkAction::WaitUntil { slot: 104 },
+        "Mindestabstand 4 Slots ab Submit-Slot 100"
+    );
+
+    // Pool ist duenner geworden → Re-Quote liefert kleineren Slice.
+    let thin = cpmm(500_000_000, 5_000_000_000);
+    let second = match exit.next_action(104, &thin) {
+        ChunkAction::Submit {
+            slice_index,
+            amount_raw,
+            ..
+        } => {
+            assert_eq!(slice_index, 1);
+            amount_raw
+        }
+        other => panic!("expected Submit, got {other:?}"),
+    };
+    assert!(
+        second < first,
+        "Re-Quote pro Slice: duennerer Pool → kleinerer Slice"
+    );
+
+    let mut slot = 104;
+    let mut last = second;
+    let mut index = 1;
+    loop {
+        exit.record_slice_fill(index, last);
+        slot += 4;
+        match exit.next_action(slot, &thin) {
+            ChunkAction::Submit {
+                slice_index,
+                amount_raw,
+                ..
+            } => {
+                index = slice_index;
+                last = amount_raw;
+            }
+            ChunkAction::Complete => break,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+    assert_eq!(exit.remaining_raw(), 0);
+    assert_eq!(exit.sold_raw(), total);
+}
+
+/// Teil-Fill: nur tatsaechlich verkaufte Menge reduziert den Rest.
+#[test]
+fn a54_partial_fill_reduces_remaining_by_actual_amount() {
+    let total = 30_000_000;
+    let manager = locked_manager(total);
+    let mut exit = ChunkedExit::start(PARENT, MINT, total, params(), &manager).unwrap();
+    let quote = cpmm(1_000_000_000, 10_000_000_000);
+    let ChunkAction::Submit { amount_raw, .. } = exit.next_action(10, &quote) else {
+        panic!("expected Submit");
+    };
+    exit.record_slice_fill(0, amount_raw / 2);
+    assert_eq!(exit.remaining_raw(), total - amount_raw / 2);
+}
+
+/// `max_slices` erreicht → letzter Slice nimmt den ganzen Rest (Exit muss terminieren).
+#[test]
+fn a54_last_allowed_slice_takes_full_remainder() {
+    let total = 500_000_000;
+    let manager = locked_manager(total);
+    let mut p = params();
+    p.max_slices = 2;
+    let mut exit = ChunkedExit::start(PARENT, MINT, total, p, &manager).unwrap();
+    let quote = cpmm(1_000_000_000, 10_000_000_000);
+
+    let ChunkAction::Submit { amount_raw, .. } = exit.next_action(0, &quote) else {
+        panic!("expected Submit");
+    };
+    exit.record_slice_fill(0, amount_raw);
+    match exit.next_action(4, &quote) {
+        ChunkAction::Submit {
+            slice_index,
+            amount_raw: last,
+            ..
+        } => {
+            assert_eq!(slice_index, 1);
+            assert_eq!(last, total - amount_raw);
+        }
+        other => panic!("expected final Submit, got {other:?}"),
+    }
+}
+
+/// Abort gibt die verbleibenden Token-Locks frei.
+#[test]
+fn a54_abort_releases_remaining_token_locks() {
+    let total = 30_000_000;
+    let manager = locked_manager(total);
+    let mut exit = ChunkedExit::start(PARENT, MINT, total, params(), &manager).unwrap();
+    let quote = cpmm(1_000_000_000, 10_000_000_000);
+    let ChunkAction::Submit { amount_raw, .. } = exit.next_action(0, &quote) else {
+        panic!("expected Submit");
+    };
+    exit.record_slice_fill(0, amount_raw);
+    manager.set_available_token_balance(MINT.to_string(), total - amount_raw);
+
+    let remaining = total - amount_raw;
+    let mut tokens = HashMap::new();
+    tokens.insert(MINT.to_string(), remaining);
+    assert!(
+        !matches!(
+            manager.try_lock_capital(LockHolder::new("other-sell"), 0, tokens.clone()),
+            LockResult::Acquired
+        ),
+        "waehrend des Exits sind die Rest-Tokens gelockt"
+    );
+
+    let summary = exit.abort(&manager);
+    assert_eq!(
+        summary,
+        ExitAbortSummary {
+            parent_intent_id: PARENT.to_string(),
+            sold_raw: amount_raw,
+            released_raw: remaining,
+            slices_submitted: 1,
+        }
+    );
+    assert!(
+        matches!(
+            manager.try_lock_capital(LockHolder::new("other-sell"), 0, tokens),
+            LockResult::Acquired
+        ),
+        "nach Abort muessen die Rest-Tokens wieder lockbar sein"
+    );
+}
+
+/// Parent/Child in DecisionRecord und ExecutionResult (JSON roundtrip).
+#[test]
+fn a54_parent_child_linkage_in_records() {
+    let child = format!("{PARENT}#slice-2");
+
+    let decision = DecisionRecord::new_rejected(
+        "execution-engine",
+        "v0.1.0",
+        "run-a54",
+        "dec-a54".to_string(),
+        child.clone(),
+        "execution-engine".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Established,
+        vec![CheckResult {
+            check_name: "simulation".to_string(),
+            passed: false,
+            reason_code: Some("SIM_FAILED".to_string()),
+            details: None,
+        }],
+        "SIM_FAILED".to_string(),
+    )
+    .with_parent_slice(PARENT, 2);
+    let decision: DecisionRecord =
+        serde_json::from_str(&serde_json::to_string(&decision).unwrap()).unwrap();
+    assert_eq!(decision.parent_intent_id.as_deref(), Some(PARENT));
+    assert_eq!(decision.slice_index, Some(2));
+
+    let result = ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-a54",
+        "exe-a54".to_string(),
+        "dec-a54".to_string(),
+        child,
+        "execution-engine".to_string(),
+        Some(MINT.to_string()),
+        Some("sig-a54".to_string()),
+        None,
+    )
+    .with_parent_slice(PARENT, 2);
+    let result: ExecutionResult =
+        serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+    assert_eq!(result.parent_intent_id.as_deref(), Some(PARENT));
+    assert_eq!(result.slice_index, Some(2));
+
+    // Unverknuepfte Records: Felder fehlen bzw. sind None (additiv).
+    let plain = ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-a54",
+        "exe-plain".to_string(),
+        "dec-plain".to_string(),
+        "intent-plain".to_string(),
+        "momentum-bot".to_string(),
+        None,
+        None,
+        None,
+    );
+    assert!(plain.parent_intent_id.is_none());
+    assert!(plain.slice_index.is_none());
+}
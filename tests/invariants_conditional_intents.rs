@@ -0,0 +1,371 @@
+//! Invariante A.59: Conditional (Trigger) Intents in der execution-engine (INVARIANTS.md A.59)
+//!
+//! Blackbox gegen `ironcrab::execution::conditional_intents` und `ironcrab::ipc`:
+//! - `ConditionalIntent` = `TradeIntent` + Trigger (tps-Schwelle auf Pool, Drawdown vom
+//!   Session-High, Expiry); Trigger sind any-of (Stop + Take-Profit = OCO).
+//! - Auswertung bei jedem `PoolCacheUpdate`/Trade des Pools ueber `tokens_per_sol`-Helfer.
+//! - Session-High nur aus Trades (I-13); PoolCache-Marks bewerten, verschieben es aber nicht.
+//! - Ausgeloeste Intents feuern genau einmal und laufen danach durch die normale Pipeline
+//!   (Simulation-Gate, I-9); Cancel/Replace per `intent_id`.
+//! - Die Zeit kommt vom Aufrufer (`observe(.., now_unix_ms)`, injizierte `Clock`, A.70); kein
+//!   Wall-Clock-Read im Buch.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; kein Sim-Bypass fuer ausgeloeste Intents.
+
+use ironcrab::execution::conditional_intents::{
+    ConditionalBook, ConditionalReject, Observation, CONDITIONAL_TRIGGER_CHECK_NAME,
+};
+use ironcrab::execution::tokens_per_sol::drawdown_from_ath_pct;
+use ironcrab::ipc::{
+    ConditionalIntent, ExplicitAmount, IntentOrigin, IntentTier, MarketEvent, MarketEventKind,
+    TradeIntent, TradeResources, TradeSide, TradingRegime, Trigger,
+};
+use ironcrab::nats::topics::TOPIC_CONDITIONAL_INTENTS;
+use ironcrab::replay::{run_pipeline_replay, PipelineReplayConfig, PIPELINE_REPLAY_DECISIONS_FILE};
+use serde_json::Value;
+use std::fs;
+use tempfile::tempdir;
+
+const POOL: &str = "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP";
+const OTHER_POOL: &str = "PoolQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQQ";
+const MINT: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Injizierte Zeit der Observations (2025-01-01T00:00:00Z).
+const NOW_MS: u64 = 1_735_689_600_000;
+
+fn sell_intent(intent_id: &str) -> TradeIntent {
+    TradeIntent::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a59",
+        intent_id.to_string(),
+        "momentum-bot",
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(1_000_000, 6),
+        TradeResources {
+            input_mint: MINT.to_string(),
+            output_mint: WSOL_MINT.to_string(),
+            pools: vec![POOL.to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Sell,
+        TradingRegime::Early,
+    )
+}
+
+/// Entry bei 1_000 tps: Stop-Loss bei -20 % (tps >= 1_250), Take-Profit bei +50 % (tps <= 666.67).
+fn stop_and_take_profit(intent_id: &str) -> ConditionalIntent {
+    ConditionalIntent {
+        intent: sell_intent(intent_id),
+        triggers: vec![
+            Trigger::TpsAtOrAbove {
+                pool: POOL.to_string(),
+                threshold_tps: 1_250.0,
+            },
+            Trigger::TpsAtOrBelow {
+                pool: POOL.to_string(),
+                threshold_tps: 666.67,
+            },
+        ],
+    }
+}
+
+fn trade(pool: &str, tps: f64, slot: u64) -> Observation {
+    Observation::Trade {
+        pool: pool.to_string(),
+        tps,
+        slot,
+    }
+}
+
+fn pool_cache(pool: &str, quote_tps: f64, slot: u64) -> Observation {
+    Observation::PoolCache {
+        pool: pool.to_string(),
+        quote_tps,
+        slot,
+    }
+}
+
+/// Zwischen den Schwellen passiert nichts; Stop-Loss feuert genau einmal.
+#[test]
+fn a59_stop_loss_fires_once_when_threshold_crossed() {
+    let mut book = ConditionalBook::new();
+    book.submit(stop_and_take_profit("cond-1"), 100).unwrap();
+    assert_eq!(book.len(), 1);
+
+    assert!(book
+        .observe(&pool_cache(POOL, 1_100.0, 101), NOW_MS)
+        .is_empty());
+    assert!(book.observe(&trade(POOL, 1_249.9, 102), NOW_MS).is_empty());
+
+    let fired = book.observe(&pool_cache(POOL, 1_300.0, 103), NOW_MS);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].intent.intent_id, "cond-1");
+    assert_eq!(fired[0].trigger_index, 0);
+    assert_eq!(fired[0].observed_tps, 1_300.0);
+    assert_eq!(fired[0].slot, 103);
+    assert_eq!(book.len(), 0, "ausgeloester Intent verlaesst das Buch");
+
+    assert!(book
+        .observe(&pool_cache(POOL, 1_400.0, 104), NOW_MS)
+        .is_empty());
+}
+
+/// Take-Profit (OCO mit Stop): das jeweils andere Bein verfaellt mit.
+#[test]
+fn a59_take_profit_fires_and_cancels_other_leg() {
+    let mut book = ConditionalBook::new();
+    book.submit(stop_and_take_profit("cond-1"), 100).unwrap();
+    let fired = book.observe(&trade(POOL, 600.0, 105), NOW_MS);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].trigger_index, 1);
+    assert!(book.observe(&trade(POOL, 2_000.0, 106), NOW_MS).is_empty());
+}
+
+/// Nur Observations des Trigger-Pools zaehlen.
+#[test]
+fn a59_only_trigger_pool_is_evaluated() {
+    let mut book = ConditionalBook::new();
+    book.submit(stop_and_take_profit("cond-1"), 100).unwrap();
+    assert!(book
+        .observe(&pool_cache(OTHER_POOL, 5_000.0, 101), NOW_MS)
+        .is_empty());
+    assert!(book
+        .observe(&trade(OTHER_POOL, 1.0, 102), NOW_MS)
+        .is_empty());
+    assert_eq!(book.len(), 1);
+}
+
+/// Trailing: Session-High folgt nur Trades (I-13); PoolCache-Marks werden gegen das
+/// Session-High bewertet, verschieben es aber nicht. Formel = `drawdown_from_ath_pct`.
+#[test]
+fn a59_trailing_drawdown_uses_trade_session_high() {
+    let mut book = ConditionalBook::new();
+    book.submit(
+        ConditionalIntent {
+            intent: sell_intent("trail-1"),
+            triggers: vec![Trigger::TrailingDrawdown {
+                pool: POOL.to_string(),
+                session_high_tps: 1_000.0,
+                drawdown_pct: 10.0,
+            }],
+        },
+        100,
+    )
+    .unwrap();
+
+    // PoolCache-Mark bei 500 darf das Session-High NICHT auf 500 ziehen.
+    assert!(book
+        .observe(&pool_cache(POOL, 500.0, 101), NOW_MS)
+        .is_empty());
+    // 1_050 ist 5 % ueber 1_000 → kein Trigger.
+    assert!(book
+        .observe(&pool_cache(POOL, 1_050.0, 102), NOW_MS)
+        .is_empty());
+
+    // Trade bei 800 setzt Session-High auf 800 (niedrigster tps).
+    assert!(book.observe(&trade(POOL, 800.0, 103), NOW_MS).is_empty());
+    assert!(drawdown_from_ath_pct(800.0, 870.0) < 10.0);
+    assert!(book
+        .observe(&pool_cache(POOL, 870.0, 104), NOW_MS)
+        .is_empty());
+
+    assert!(drawdown_from_ath_pct(800.0, 890.0) >= 10.0);
+    let fired = book.observe(&pool_cache(POOL, 890.0, 105), NOW_MS);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].intent.intent_id, "trail-1");
+}
+
+/// Expiry feuert bei `slot >= expiry_slot` auf jeder Observation inkl. Slot-Tick.
+#[test]
+fn a59_expiry_fires_on_slot_tick() {
+    let mut book = ConditionalBook::new();
+    book.submit(
+        ConditionalIntent {
+            intent: sell_intent("exp-1"),
+            triggers: vec![Trigger::Expiry { slot: 200 }],
+        },
+        100,
+    )
+    .unwrap();
+    assert!(book
+        .observe(&Observation::Slot { slot: 199 }, NOW_MS)
+        .is_empty());
+    let fired = book.observe(&Observation::Slot { slot: 200 }, NOW_MS);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].trigger_index, 0);
+}
+
+/// Cancel/Replace per intent_id; doppelte intent_id und leere Trigger werden abgelehnt.
+#[test]
+fn a59_cancel_replace_by_intent_id() {
+    let mut book = ConditionalBook::new();
+    book.submit(stop_and_take_profit("cond-1"), 100).unwrap();
+    assert_eq!(
+        book.submit(stop_and_take_profit("cond-1"), 101),
+        Err(ConditionalReject::DuplicateIntentId)
+    );
+    let mut empty = stop_and_take_profit("cond-2");
+    empty.triggers.clear();
+    assert_eq!(book.submit(empty, 101), Err(ConditionalReject::NoTriggers));
+
+    // Replace: Stop nachziehen (Break-even), gleiche intent_id.
+    let mut tightened = stop_and_take_profit("cond-1");
+    tightened.triggers[0] = Trigger::TpsAtOrAbove {
+        pool: POOL.to_string(),
+        threshold_tps: 1_000.0,
+    };
+    assert!(book.replace("cond-1", tightened, 102).is_ok());
+    assert_eq!(
+        book.observe(&pool_cache(POOL, 1_100.0, 103), NOW_MS).len(),
+        1
+    );
+
+    book.submit(stop_and_take_profit("cond-3"), 104).unwrap();
+    assert!(book.cancel("cond-3"));
+    assert!(!book.cancel("cond-3"));
+    assert_eq!(
+        book.replace("cond-3", stop_and_take_profit("cond-3"), 105),
+        Err(ConditionalReject::UnknownIntentId)
+    );
+    assert!(book
+        .observe(&pool_cache(POOL, 5_000.0, 106), NOW_MS)
+        .is_empty());
+}
+
+/// Ausgeloeste Intents gehen durch die normale Pipeline: TTL laeuft ab dem injizierten
+/// Trigger-Zeitpunkt, DecisionRecord bekommt einen passed-Check `conditional_trigger` VOR `simulation`.
+#[test]
+fn a59_fired_intent_enters_normal_pipeline() {
+    let mut cond = stop_and_take_profit("cond-1");
+    cond.intent.header.ts_unix_ms = NOW_MS - 60_000;
+
+    let mut book = ConditionalBook::new();
+    book.submit(cond, 100).unwrap();
+    let fired = book
+        .observe(&pool_cache(POOL, 1_300.0, 103), NOW_MS + 7)
+        .remove(0);
+
+    assert_eq!(fired.intent.side, TradeSide::Sell);
+    assert_eq!(fired.intent.ttl_ms, 5_000);
+    assert_eq!(
+        fired.intent.header.ts_unix_ms,
+        NOW_MS + 7,
+        "Header-Zeitstempel = injizierte Trigger-Zeit, nicht Wall-Clock"
+    );
+
+    let check = fired.to_check_result();
+    assert_eq!(CONDITIONAL_TRIGGER_CHECK_NAME, "conditional_trigger");
+    assert_eq!(check.check_name, CONDITIONAL_TRIGGER_CHECK_NAME);
+    assert!(check.passed);
+    assert!(check.reason_code.is_none());
+    let details = check.details.expect("details mit Trigger/tps/slot");
+    assert!(details.contains("1300"), "{details}");
+}
+
+fn slot_event(i: u64) -> MarketEvent {
+    let slot = 300_000_000 + i;
+    let mut e = MarketEvent::new(
+        "market-data",
+        "eval-a59",
+        "run-recorded",
+        format!("ev-slot-{i:02}"),
+        "geyser",
+        Some(slot),
+        MarketEventKind::SlotUpdate { current_slot: slot },
+    );
+    e.header.ts_unix_ms = NOW_MS + i * 400;
+    e
+}
+
+/// Echter Gate-Pfad: Der Full-Pipeline-Replay (A.70) speist den ausgeloesten Intent in die
+/// execution-engine. Der DecisionRecord traegt `conditional_trigger` vor `simulation`, die TTL
+/// wird gegen den virtuellen Trigger-Zeitpunkt geprueft (Intent war 60 s vorher hinterlegt), und
+/// ohne bestandene Simulation wird nichts gesendet (I-9).
+#[test]
+fn a59_fired_intent_runs_through_engine_gates() {
+    let dir = tempdir().unwrap();
+    let events_path = dir.path().join("market_events-20250101.jsonl");
+    let body: String = (0..10u64)
+        .map(|i| serde_json::to_string(&slot_event(i)).unwrap() + "\n")
+        .collect();
+    fs::write(&events_path, body).unwrap();
+
+    let mut cond = ConditionalIntent {
+        intent: sell_intent("cond-gate-1"),
+        triggers: vec![Trigger::Expiry { slot: 300_000_005 }],
+    };
+    cond.intent.header.ts_unix_ms = NOW_MS - 60_000;
+
+    let out = dir.path().join("out");
+    let config = PipelineReplayConfig::new(events_path, out.clone())
+        .with_strategies(vec![])
+        .with_conditional_intents(vec![cond])
+        .with_run_id("conditional-a59");
+    let report = run_pipeline_replay(&config).expect("pipeline replay");
+    assert_eq!(report.intents, 1, "nur der ausgeloeste Conditional Intent");
+    assert_eq!(report.decisions, 1);
+
+    let decisions: Vec<Value> = fs::read_to_string(out.join(PIPELINE_REPLAY_DECISIONS_FILE))
+        .unwrap()
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(decisions.len(), 1);
+    let decision = &decisions[0];
+    assert_eq!(decision["intent_id"], "cond-gate-1");
+    assert_eq!(
+        decision["ts_unix_ms"],
+        NOW_MS + 5 * 400,
+        "Decision zur virtuellen Zeit des ausloesenden Events"
+    );
+
+    let checks = decision["checks"].as_array().expect("checks[]");
+    let position = |name: &str| checks.iter().position(|c| c["check_name"] == name);
+    let trigger = position(CONDITIONAL_TRIGGER_CHECK_NAME).expect("conditional_trigger-Check");
+    assert_eq!(checks[trigger]["passed"], true);
+    let ttl = position("ttl_valid").expect("TTL-Gate durchlaufen");
+    assert_eq!(
+        checks[ttl]["passed"], true,
+        "TTL ab Trigger-Zeitpunkt, nicht ab Hinterlegung"
+    );
+    if let Some(sim) = position("simulation") {
+        assert!(trigger < sim, "conditional_trigger vor simulation");
+    }
+    let sim_passed = checks
+        .iter()
+        .any(|c| c["check_name"] == "simulation" && c["passed"] == true);
+    assert!(
+        decision["outcome"] == "Rejected" || decision["outcome"] == "SimFailed" || sim_passed,
+        "kein Sim-Bypass (I-9): {decision}"
+    );
+}
+
+/// Wire + Persistenz: ConditionalIntent JSON-roundtrip, Buch-Snapshot ueberlebt Restart.
+#[test]
+fn a59_wire_and_snapshot_roundtrip() {
+    assert_eq!(TOPIC_CONDITIONAL_INTENTS, "ironcrab.v1.conditional_intents");
+    let cond = stop_and_take_profit("cond-1");
+    let json = serde_json::to_value(&cond).unwrap();
+    assert_eq!(json["triggers"][0]["kind"], "tps_at_or_above");
+    let parsed: ConditionalIntent = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, cond);
+
+    let mut book = ConditionalBook::new();
+    book.submit(cond, 100).unwrap();
+    book.observe(&trade(POOL, 900.0, 101), NOW_MS);
+    let snapshot = serde_json::to_string(&book.snapshot()).unwrap();
+    let mut restored = ConditionalBook::restore(serde_json::from_str(&snapshot).unwrap());
+    assert_eq!(restored.len(), 1);
+    assert_eq!(
+        restored
+            .observe(&pool_cache(POOL, 1_300.0, 102), NOW_MS)
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,339 @@
+//! Invariante A.71: Strukturierter Diff von Decision-Streams (INVARIANTS.md A.71)
+//!
+//! Blackbox gegen `ironcrab::replay::decision_diff`:
+//! - Zwei Decision-Record-Streams (JSONL) werden per `intent_id` ausgerichtet
+//!   (bei Retries: k-tes Vorkommen gegen k-tes Vorkommen).
+//! - Gemeldet werden fehlende/zusaetzliche Decisions, Outcome-Aenderungen, Aenderungen des
+//!   primaeren Reject-Grunds, Pass/Fail-Flips und Reason-Code-Aenderungen pro Check.
+//! - Maschinenlesbare Summary (JSON) fuer reichere Golden-Replay-Fehlermeldungen (A.10).
+//! - Eingabe ist das Golden-Format (nur `intent_id`, `outcome`, `primary_reject_reason`, `checks`);
+//!   `decision_id`, Header und `details` sind nicht Teil des Vergleichs.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Golden-Fixtures werden nur gelesen.
+
+use ironcrab::replay::decision_diff::{
+    diff_decision_files, diff_decision_jsonl, DecisionDiff, DecisionDiffEntry, DiffError,
+};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+fn golden(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("golden_replays")
+        .join(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path:?}: {e}"))
+}
+
+fn records(jsonl: &str) -> Vec<Value> {
+    jsonl
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+fn to_jsonl(records: &[Value]) -> String {
+    records
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap() + "\n")
+        .collect()
+}
+
+fn check_mut<'a>(record: &'a mut Value, check_name: &str) -> &'a mut Value {
+    record["checks"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|c| c["check_name"] == check_name)
+        .unwrap_or_else(|| panic!("check {check_name} fehlt"))
+}
+
+fn diff(expected: &[Value], actual: &[Value]) -> DecisionDiff {
+    diff_decision_jsonl(&to_jsonl(expected), &to_jsonl(actual)).expect("diff")
+}
+
+/// Identische Streams → leerer Diff; `decision_id`, Header und `details` werden ignoriert.
+#[test]
+fn a71_identical_streams_produce_empty_diff() {
+    for name in [
+        "rejected_trade_expected.jsonl",
+        "normal_trade_simsucc_expected.jsonl",
+        "sim_failed_expected.jsonl",
+        "liquidation_6005_retry_expected.jsonl",
+    ] {
+        let expected = records(&golden(name));
+        let mut actual = expected.clone();
+        for r in &mut actual {
+            r["decision_id"] = json!("dec-random-uuid");
+            r["ts_unix_ms"] = json!(1_900_000_000_000u64);
+            r["run_id"] = json!("another-run");
+            for c in r["checks"].as_array_mut().unwrap() {
+                c["details"] = json!("free text differs between runs");
+            }
+        }
+        let d = diff(&expected, &actual);
+        assert!(d.is_empty(), "{name}: {:?}", d.entries);
+        assert_eq!(d.summary.expected, expected.len() as u64);
+        assert_eq!(d.summary.actual, expected.len() as u64);
+        assert_eq!(d.summary.matched, expected.len() as u64);
+    }
+}
+
+/// Fehlende und zusaetzliche Decisions werden per `intent_id` gemeldet.
+#[test]
+fn a71_missing_and_added_decisions_are_reported() {
+    let expected = records(&golden("rejected_trade_expected.jsonl"));
+    let mut actual = vec![expected[1].clone()];
+    let mut extra = expected[0].clone();
+    extra["intent_id"] = json!("golden-rejected-999");
+    actual.push(extra);
+
+    let d = diff(&expected, &actual);
+    assert_eq!(
+        d.entries,
+        vec![
+            DecisionDiffEntry::Missing {
+                intent_id: "golden-rejected-001".to_string(),
+            },
+            DecisionDiffEntry::Added {
+                intent_id: "golden-rejected-999".to_string(),
+            },
+        ],
+        "Reihenfolge: Expected-Stream, danach Zusaetze in Actual-Reihenfolge"
+    );
+    assert_eq!(d.summary.missing, 1);
+    assert_eq!(d.summary.added, 1);
+    assert_eq!(d.summary.matched, 1);
+}
+
+/// Umsortierte Streams sind kein Diff: ausgerichtet wird per `intent_id`, nicht per Position.
+#[test]
+fn a71_alignment_is_by_intent_id_not_position() {
+    let expected = records(&golden("rejected_trade_expected.jsonl"));
+    let actual: Vec<Value> = expected.iter().rev().cloned().collect();
+    let d = diff(&expected, &actual);
+    assert!(d.is_empty(), "{:?}", d.entries);
+}
+
+/// Outcome- und Primaergrund-Aenderungen werden getrennt gemeldet.
+#[test]
+fn a71_outcome_and_primary_reason_changes() {
+    let expected = records(&golden("normal_trade_simsucc_expected.jsonl"));
+    let mut actual = expected.clone();
+    actual[0]["outcome"] = json!("Planned");
+    actual[0]["primary_reject_reason"] = Value::Null;
+
+    let d = diff(&expected, &actual);
+    assert_eq!(
+        d.entries,
+        vec![
+            DecisionDiffEntry::OutcomeChanged {
+                intent_id: "golden-simsucc-001".to_string(),
+                expected: "Rejected".to_string(),
+                actual: "Planned".to_string(),
+            },
+            DecisionDiffEntry::PrimaryReasonChanged {
+                intent_id: "golden-simsucc-001".to_string(),
+                expected: Some("send_disabled".to_string()),
+                actual: None,
+            },
+        ]
+    );
+    assert_eq!(d.summary.outcome_changed, 1);
+    assert_eq!(d.summary.reason_changed, 1);
+}
+
+/// Check-Ebene: Pass/Fail-Flip, Reason-Code-Aenderung, fehlender und zusaetzlicher Check.
+#[test]
+fn a71_check_level_flips_and_reason_changes() {
+    let expected = records(&golden("rejected_trade_expected.jsonl"));
+    let mut actual = expected.clone();
+    {
+        let c = check_mut(&mut actual[0], "max_position_size");
+        c["passed"] = json!(true);
+        c["reason_code"] = Value::Null;
+    }
+    check_mut(&mut actual[1], "max_slippage")["reason_code"] = json!("RISK_MAX_SLIPPAGE");
+    actual[1]["checks"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|c| c["check_name"] != "kill_switch");
+    actual[1]["checks"].as_array_mut().unwrap().push(json!({
+        "check_name": "circuit_breaker",
+        "passed": true,
+        "reason_code": null,
+        "details": null
+    }));
+
+    let d = diff(&expected, &actual);
+    assert_eq!(
+        d.entries,
+        vec![
+            DecisionDiffEntry::CheckFlipped {
+                intent_id: "golden-rejected-001".to_string(),
+                check_name: "max_position_size".to_string(),
+                expected_passed: false,
+                actual_passed: true,
+            },
+            DecisionDiffEntry::CheckReasonChanged {
+                intent_id: "golden-rejected-001".to_string(),
+                check_name: "max_position_size".to_string(),
+                expected: Some("RISK_MAX_POSITION".to_string()),
+                actual: None,
+            },
+            DecisionDiffEntry::CheckMissing {
+                intent_id: "golden-rejected-002".to_string(),
+                check_name: "kill_switch".to_string(),
+            },
+            DecisionDiffEntry::CheckReasonChanged {
+                intent_id: "golden-rejected-002".to_string(),
+                check_name: "max_slippage".to_string(),
+                expected: Some("SIM_SLIPPAGE_EXCEEDED".to_string()),
+                actual: Some("RISK_MAX_SLIPPAGE".to_string()),
+            },
+            DecisionDiffEntry::CheckAdded {
+                intent_id: "golden-rejected-002".to_string(),
+                check_name: "circuit_breaker".to_string(),
+            },
+        ],
+        "pro Decision: Checks in Expected-Reihenfolge, danach zusaetzliche Checks"
+    );
+    assert_eq!(d.summary.check_flips, 1);
+    assert_eq!(d.summary.check_reason_changed, 2);
+    assert_eq!(d.summary.checks_missing, 1);
+    assert_eq!(d.summary.checks_added, 1);
+    assert_eq!(d.summary.matched, 2);
+}
+
+/// Mehrere Decisions pro Intent (Retry): k-tes Vorkommen gegen k-tes Vorkommen.
+#[test]
+fn a71_retries_align_by_occurrence() {
+    let expected = records(&golden("liquidation_6005_retry_expected.jsonl"));
+    assert_eq!(expected[0]["intent_id"], expected[1]["intent_id"]);
+
+    let mut actual = expected.clone();
+    actual[1]["primary_reject_reason"] = json!("Custom program error: 0x2");
+    let d = diff(&expected, &actual);
+    assert_eq!(
+        d.entries,
+        vec![DecisionDiffEntry::PrimaryReasonChanged {
+            intent_id: "golden-6005-001".to_string(),
+            expected: Some("Custom program error: 0x1".to_string()),
+            actual: Some("Custom program error: 0x2".to_string()),
+        }],
+        "zweites Vorkommen wird mit dem zweiten verglichen"
+    );
+
+    let d = diff(&expected, &expected[..1]);
+    assert_eq!(
+        d.entries,
+        vec![DecisionDiffEntry::Missing {
+            intent_id: "golden-6005-001".to_string(),
+        }],
+        "fehlender Retry ist Missing, kein Feld-Diff"
+    );
+}
+
+/// Summary und Eintraege sind maschinenlesbar (stabiles JSON-Format).
+#[test]
+fn a71_summary_is_machine_readable_json() {
+    let expected = records(&golden("rejected_trade_expected.jsonl"));
+    let mut actual = expected.clone();
+    actual[0]["outcome"] = json!("Planned");
+    actual.remove(1);
+
+    let d = diff(&expected, &actual);
+    let v = serde_json::to_value(&d).unwrap();
+    assert_eq!(v["summary"]["expected"], 2);
+    assert_eq!(v["summary"]["actual"], 1);
+    assert_eq!(v["summary"]["matched"], 1);
+    assert_eq!(v["summary"]["missing"], 1);
+    assert_eq!(v["summary"]["outcome_changed"], 1);
+    for key in [
+        "added",
+        "reason_changed",
+        "check_flips",
+        "check_reason_changed",
+        "checks_added",
+        "checks_missing",
+    ] {
+        assert_eq!(v["summary"][key], 0, "summary.{key}");
+    }
+    assert_eq!(
+        v["entries"][0],
+        json!({
+            "kind": "outcome_changed",
+            "intent_id": "golden-rejected-001",
+            "expected": "Rejected",
+            "actual": "Planned"
+        })
+    );
+    assert_eq!(v["entries"][1]["kind"], "missing");
+
+    let back: DecisionDiff = serde_json::from_value(v).unwrap();
+    assert_eq!(back, d);
+}
+
+/// Datei-Variante und Fehlerfaelle: kaputte Zeile nennt die Zeilennummer, fehlende `intent_id` ist Fehler.
+#[test]
+fn a71_file_input_and_parse_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("expected.jsonl");
+    let b = dir.path().join("actual.jsonl");
+    fs::write(&a, golden("sim_failed_expected.jsonl")).unwrap();
+    fs::write(&b, golden("sim_failed_expected.jsonl")).unwrap();
+    assert!(diff_decision_files(&a, &b).unwrap().is_empty());
+
+    let broken = golden("sim_failed_expected.jsonl") + "\n{not json\n";
+    match diff_decision_jsonl(&golden("sim_failed_expected.jsonl"), &broken) {
+        Err(DiffError::Parse { line, .. }) => assert_eq!(line, 3, "1-basiert, Leerzeilen zaehlen"),
+        other => panic!("expected Parse error, got {other:?}"),
+    }
+
+    let no_id = to_jsonl(&[json!({"outcome": "Rejected", "checks": []})]);
+    assert!(matches!(
+        diff_decision_jsonl(&no_id, &no_id),
+        Err(DiffError::MissingIntentId { line: 1 })
+    ));
+
+    assert!(matches!(
+        diff_decision_files(&dir.path().join("nope.jsonl"), &b),
+        Err(DiffError::Io { .. })
+    ));
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: `ironcrab-cli decisions diff` nutzt die Lib und bietet JSON-Ausgabe.
+#[test]
+fn a71_cli_exposes_decision_diff() {
+    let path = iron_crab_bin_rs("ironcrab_cli");
+    let Ok(src) = fs::read_to_string(&path) else {
+        eprintln!("SKIP: {path:?} nicht vorhanden (Geschwister-Layout fehlt)");
+        return;
+    };
+    assert!(
+        src.contains("diff_decision_files"),
+        "CLI muss decision_diff::diff_decision_files nutzen (keine zweite Vergleichslogik)"
+    );
+    assert!(
+        src.contains("\"decisions\""),
+        "Subcommand `decisions` fehlt"
+    );
+    assert!(
+        src.contains("\"diff\""),
+        "Subcommand `decisions diff` fehlt"
+    );
+    assert!(src.contains("--json"), "Flag --json fehlt");
+}
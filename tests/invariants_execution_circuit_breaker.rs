@@ -0,0 +1,476 @@
+//! Invariante A.50: Automatischer Circuit Breaker in der execution-engine (INVARIANTS.md A.50)
+//!
+//! Blackbox gegen `ironcrab::execution::circuit_breaker` (reine State-Machine, kein NATS/RPC):
+//! - Trip-Bedingungen: SimFailed-Streak, Error-Code-Burst, realisierter Verlust im Rolling Window,
+//!   Confirm-Latenz ueber Schwelle.
+//! - Scope: betroffene Source, betroffene DEX oder global.
+//! - Half-Open: nach Cooldown begrenzte Probe-Intents; Erfolg schliesst, Fehler oeffnet erneut.
+//! - Wire: `CircuitBreakerEvent` auf `TOPIC_CIRCUIT_BREAKER_EVENTS`, Reject `CIRCUIT_BREAKER_OPEN`.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Zeit wird als `now_ms` injiziert
+//! (kein Wall-Clock im Test, Replay-Determinismus).
+
+use ironcrab::execution::circuit_breaker::{
+    BreakerAdmission, BreakerObservation, BreakerScope, BreakerState, BreakerTransition,
+    CircuitBreaker, CircuitBreakerConfig, ErrorCodeBurstConfig, RealizedLossWindowConfig,
+    TripReason, CIRCUIT_BREAKER_CHECK_NAME,
+};
+use ironcrab::ipc::{CircuitBreakerEvent, RejectReason};
+use ironcrab::nats::topics::TOPIC_CIRCUIT_BREAKER_EVENTS;
+
+const SRC_MOMENTUM: &str = "momentum-bot";
+const SRC_ARB: &str = "arb-strategy";
+const DEX_PUMPFUN: &str = "pumpfun";
+const DEX_PUMP_AMM: &str = "pump_amm";
+
+fn sim_failed(code: &str) -> BreakerObservation {
+    BreakerObservation::SimFailed {
+        error_code: Some(code.to_string()),
+    }
+}
+
+/// Seit dem letzten Aufruf angefallene Transitionen (nur die Art).
+fn drained(breaker: &mut CircuitBreaker) -> Vec<BreakerTransition> {
+    breaker
+        .take_transitions()
+        .into_iter()
+        .map(|t| t.transition)
+        .collect()
+}
+
+/// Nur SimFailed-Streak aktiv (alle anderen Bedingungen aus), Cooldown 10 s, 1 Probe.
+fn streak_only_config(streak: u32) -> CircuitBreakerConfig {
+    CircuitBreakerConfig {
+        enabled: true,
+        sim_failed_streak: Some(streak),
+        error_code_burst: None,
+        realized_loss_window: None,
+        confirm_latency_ms_max: None,
+        cooldown_ms: 10_000,
+        half_open_probes: 1,
+    }
+}
+
+/// N-1 SimFailed trippen nicht; der N-te trippt genau die betroffene Source.
+#[test]
+fn a50_sim_failed_streak_trips_affected_source_only() {
+    let mut breaker = CircuitBreaker::new(streak_only_config(3));
+
+    for i in 0..2u64 {
+        let trip = breaker.record(
+            SRC_MOMENTUM,
+            DEX_PUMPFUN,
+            sim_failed("Custom(1)"),
+            1_000 + i,
+        );
+        assert!(trip.is_none(), "Streak {} < 3 darf nicht trippen", i + 1);
+    }
+    let trip = breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 1_002)
+        .expect("dritter SimFailed in Folge muss trippen");
+
+    assert_eq!(trip.scope, BreakerScope::Source(SRC_MOMENTUM.to_string()));
+    assert_eq!(trip.reason, TripReason::SimFailedStreak);
+    assert_eq!(trip.transition, BreakerTransition::Tripped);
+
+    assert!(
+        matches!(
+            breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 1_003),
+            BreakerAdmission::Blocked { .. }
+        ),
+        "getrippte Source muss geblockt sein"
+    );
+    assert_eq!(
+        breaker.admit(SRC_ARB, DEX_PUMP_AMM, 1_003),
+        BreakerAdmission::Allowed,
+        "andere Source auf anderer DEX bleibt handelbar"
+    );
+}
+
+/// Ein Erfolg zwischen Fehlschlaegen setzt den Streak zurueck (konsekutiv, nicht kumulativ).
+#[test]
+fn a50_success_resets_sim_failed_streak() {
+    let mut breaker = CircuitBreaker::new(streak_only_config(3));
+
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 1)
+        .is_none());
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 2)
+        .is_none());
+    assert!(breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMPFUN,
+            BreakerObservation::SimSucceeded,
+            3
+        )
+        .is_none());
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 4)
+        .is_none());
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 5)
+        .is_none());
+
+    assert_eq!(
+        breaker.state(&BreakerScope::Source(SRC_MOMENTUM.to_string())),
+        BreakerState::Closed
+    );
+}
+
+/// Wiederholter Error-Code (z. B. `Custom(6024)`) innerhalb des Fensters trippt die DEX,
+/// unabhaengig von der Source. Vorkommen ausserhalb des Fensters zaehlen nicht.
+#[test]
+fn a50_error_code_burst_trips_dex_scope_within_window() {
+    let config = CircuitBreakerConfig {
+        enabled: true,
+        sim_failed_streak: None,
+        error_code_burst: Some(ErrorCodeBurstConfig {
+            error_code: "Custom(6024)".to_string(),
+            count: 3,
+            window_ms: 60_000,
+        }),
+        realized_loss_window: None,
+        confirm_latency_ms_max: None,
+        cooldown_ms: 30_000,
+        half_open_probes: 1,
+    };
+    let mut breaker = CircuitBreaker::new(config);
+
+    // Ein Vorkommen weit vor dem Fenster.
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(6024)"), 0)
+        .is_none());
+    // Anderer Code zaehlt nicht zum Burst.
+    assert!(breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMPFUN,
+            sim_failed("Custom(6005)"),
+            100_000
+        )
+        .is_none());
+    assert!(breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMPFUN,
+            sim_failed("Custom(6024)"),
+            100_001
+        )
+        .is_none());
+    assert!(
+        breaker
+            .record(SRC_ARB, DEX_PUMPFUN, sim_failed("Custom(6024)"), 100_002)
+            .is_none(),
+        "zwei Vorkommen im Fenster (das bei t=0 ist abgelaufen) duerfen nicht trippen"
+    );
+    let trip = breaker
+        .record(SRC_ARB, DEX_PUMPFUN, sim_failed("Custom(6024)"), 100_003)
+        .expect("drittes Custom(6024) im Fenster muss trippen");
+
+    assert_eq!(trip.scope, BreakerScope::Dex(DEX_PUMPFUN.to_string()));
+    assert_eq!(trip.reason, TripReason::ErrorCodeBurst);
+    assert!(matches!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 100_004),
+        BreakerAdmission::Blocked { .. }
+    ));
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMP_AMM, 100_004),
+        BreakerAdmission::Allowed,
+        "andere DEX derselben Source bleibt offen"
+    );
+}
+
+/// Realisierter Nettoverlust im Rolling Window trippt global; Gewinne verrechnen sich.
+#[test]
+fn a50_realized_loss_window_trips_global_scope() {
+    let config = CircuitBreakerConfig {
+        enabled: true,
+        sim_failed_streak: None,
+        error_code_burst: None,
+        realized_loss_window: Some(RealizedLossWindowConfig {
+            max_loss_lamports: 1_000_000_000,
+            window_ms: 3_600_000,
+        }),
+        confirm_latency_ms_max: None,
+        cooldown_ms: 60_000,
+        half_open_probes: 1,
+    };
+    let mut breaker = CircuitBreaker::new(config);
+
+    let confirmed = |pnl: i64| BreakerObservation::Confirmed {
+        net_pnl_lamports: pnl,
+        confirm_latency_ms: 500,
+    };
+
+    assert!(breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, confirmed(-600_000_000), 1_000)
+        .is_none());
+    assert!(breaker
+        .record(SRC_ARB, DEX_PUMP_AMM, confirmed(200_000_000), 2_000)
+        .is_none());
+    assert!(
+        breaker
+            .record(SRC_MOMENTUM, DEX_PUMPFUN, confirmed(-500_000_000), 3_000)
+            .is_none(),
+        "Netto -900M < Limit 1B: noch kein Trip"
+    );
+    let trip = breaker
+        .record(SRC_ARB, DEX_PUMP_AMM, confirmed(-200_000_000), 4_000)
+        .expect("Netto -1.1B im Fenster muss trippen");
+
+    assert_eq!(trip.scope, BreakerScope::Global);
+    assert_eq!(trip.reason, TripReason::RealizedLossWindow);
+    for (src, dex) in [(SRC_MOMENTUM, DEX_PUMPFUN), (SRC_ARB, DEX_PUMP_AMM)] {
+        assert!(
+            matches!(
+                breaker.admit(src, dex, 4_001),
+                BreakerAdmission::Blocked { .. }
+            ),
+            "globaler Trip blockt {src}/{dex}"
+        );
+    }
+}
+
+/// Confirm-Latenz ueber Schwelle trippt die DEX (Landing-Problem ist pfadbezogen).
+#[test]
+fn a50_confirm_latency_above_threshold_trips() {
+    let config = CircuitBreakerConfig {
+        enabled: true,
+        sim_failed_streak: None,
+        error_code_burst: None,
+        realized_loss_window: None,
+        confirm_latency_ms_max: Some(20_000),
+        cooldown_ms: 60_000,
+        half_open_probes: 1,
+    };
+    let mut breaker = CircuitBreaker::new(config);
+
+    assert!(breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMP_AMM,
+            BreakerObservation::Confirmed {
+                net_pnl_lamports: 0,
+                confirm_latency_ms: 20_000,
+            },
+            1,
+        )
+        .is_none());
+    let trip = breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMP_AMM,
+            BreakerObservation::Confirmed {
+                net_pnl_lamports: 0,
+                confirm_latency_ms: 20_001,
+            },
+            2,
+        )
+        .expect("Latenz > Schwelle muss trippen");
+    assert_eq!(trip.scope, BreakerScope::Dex(DEX_PUMP_AMM.to_string()));
+    assert_eq!(trip.reason, TripReason::ConfirmLatency);
+}
+
+/// Nach Cooldown: Half-Open laesst genau `half_open_probes` Intents durch; Probe-Erfolg schliesst.
+/// Jede Transition (auch Open → HalfOpen aus `admit`) erscheint genau einmal in `take_transitions`.
+#[test]
+fn a50_half_open_allows_bounded_probes_then_closes_on_success() {
+    let mut config = streak_only_config(1);
+    config.half_open_probes = 2;
+    let mut breaker = CircuitBreaker::new(config);
+    let scope = BreakerScope::Source(SRC_MOMENTUM.to_string());
+
+    breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 0)
+        .expect("Streak 1 trippt sofort");
+    assert_eq!(drained(&mut breaker), vec![BreakerTransition::Tripped]);
+
+    assert!(
+        matches!(
+            breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 9_999),
+            BreakerAdmission::Blocked { .. }
+        ),
+        "vor Cooldown-Ende bleibt Open"
+    );
+    assert!(breaker.take_transitions().is_empty());
+
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_000),
+        BreakerAdmission::Probe
+    );
+    let half_open = breaker.take_transitions();
+    assert_eq!(half_open.len(), 1, "Open → HalfOpen wird gemeldet");
+    assert_eq!(half_open[0].transition, BreakerTransition::HalfOpen);
+    assert_eq!(half_open[0].scope, scope);
+    assert_eq!(half_open[0].reason, TripReason::SimFailedStreak);
+    assert_eq!(half_open[0].at_ms, 10_000);
+
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_001),
+        BreakerAdmission::Probe
+    );
+    assert!(
+        matches!(
+            breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_002),
+            BreakerAdmission::Blocked { .. }
+        ),
+        "mehr Probes als konfiguriert duerfen nicht durch"
+    );
+    assert!(matches!(
+        breaker.state(&scope),
+        BreakerState::HalfOpen { .. }
+    ));
+
+    let transition = breaker
+        .record(
+            SRC_MOMENTUM,
+            DEX_PUMPFUN,
+            BreakerObservation::SimSucceeded,
+            10_100,
+        )
+        .expect("Probe-Erfolg muss Transition melden");
+    assert_eq!(transition.transition, BreakerTransition::Closed);
+    assert_eq!(
+        drained(&mut breaker),
+        vec![BreakerTransition::Closed],
+        "keine doppelte HalfOpen-Meldung fuer weitere Probes"
+    );
+    assert_eq!(breaker.state(&scope), BreakerState::Closed);
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_101),
+        BreakerAdmission::Allowed
+    );
+}
+
+/// Fehlschlag einer Probe oeffnet erneut mit neuem Cooldown ab dem Fehlschlag.
+#[test]
+fn a50_half_open_probe_failure_reopens_with_fresh_cooldown() {
+    let mut breaker = CircuitBreaker::new(streak_only_config(1));
+    let scope = BreakerScope::Source(SRC_MOMENTUM.to_string());
+
+    breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 0)
+        .expect("trip");
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_000),
+        BreakerAdmission::Probe
+    );
+
+    let reopen = breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 10_500)
+        .expect("Probe-Fehlschlag muss Transition melden");
+    assert_eq!(reopen.transition, BreakerTransition::Tripped);
+    assert!(matches!(breaker.state(&scope), BreakerState::Open { .. }));
+
+    assert!(
+        matches!(
+            breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 20_000),
+            BreakerAdmission::Blocked { .. }
+        ),
+        "neuer Cooldown zaehlt ab Probe-Fehlschlag (10_500 + 10_000)"
+    );
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 20_500),
+        BreakerAdmission::Probe
+    );
+}
+
+/// DecisionRecord-Check: Blocked → failed `circuit_breaker` mit `CIRCUIT_BREAKER_OPEN`, Scope und Trip-Grund.
+#[test]
+fn a50_admission_maps_to_circuit_breaker_check() {
+    assert_eq!(CIRCUIT_BREAKER_CHECK_NAME, "circuit_breaker");
+    let mut breaker = CircuitBreaker::new(streak_only_config(1));
+    let allowed = breaker
+        .admit(SRC_MOMENTUM, DEX_PUMPFUN, 0)
+        .to_check_result();
+    assert_eq!(allowed.check_name, CIRCUIT_BREAKER_CHECK_NAME);
+    assert!(allowed.passed);
+    assert!(allowed.reason_code.is_none());
+
+    breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 1)
+        .expect("trip");
+    let blocked = breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 2);
+    assert!(matches!(blocked, BreakerAdmission::Blocked { .. }));
+    let check = blocked.to_check_result();
+    assert_eq!(check.check_name, CIRCUIT_BREAKER_CHECK_NAME);
+    assert!(!check.passed);
+    assert_eq!(check.reason_code.as_deref(), Some("CIRCUIT_BREAKER_OPEN"));
+    let details = check.details.expect("Details mit Scope und Trip-Grund");
+    assert!(details.contains(SRC_MOMENTUM), "{details}");
+    assert!(details.contains("BREAKER_SIM_FAILED_STREAK"), "{details}");
+
+    let probe = breaker
+        .admit(SRC_MOMENTUM, DEX_PUMPFUN, 10_001)
+        .to_check_result();
+    assert!(probe.passed, "Probe laeuft durch die normale Pipeline");
+}
+
+/// Default-Konfiguration ist deaktiviert: kein Trip, keine Admission-Aenderung.
+/// Dadurch bleiben Golden Replays (A.10) ohne `circuit_breaker`-Check unveraendert.
+#[test]
+fn a50_default_config_is_disabled_and_never_trips() {
+    let config = CircuitBreakerConfig::default();
+    assert!(!config.enabled, "Breaker muss explizit aktiviert werden");
+
+    let mut breaker = CircuitBreaker::new(config);
+    for t in 0..50u64 {
+        assert!(breaker
+            .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(6024)"), t)
+            .is_none());
+    }
+    assert_eq!(
+        breaker.admit(SRC_MOMENTUM, DEX_PUMPFUN, 51),
+        BreakerAdmission::Allowed
+    );
+}
+
+/// Wire-Vertrag: Reject-Code, Trip-Reason-Codes, Event-Roundtrip und versioniertes Topic.
+#[test]
+fn a50_breaker_event_and_reason_codes_wire_contract() {
+    assert_eq!(
+        TOPIC_CIRCUIT_BREAKER_EVENTS,
+        "ironcrab.v1.control.circuit_breaker"
+    );
+
+    let json = serde_json::to_string(&RejectReason::CircuitBreakerOpen).unwrap();
+    assert_eq!(json, "\"CIRCUIT_BREAKER_OPEN\"");
+    let parsed: RejectReason = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, RejectReason::CircuitBreakerOpen);
+
+    assert_eq!(
+        TripReason::SimFailedStreak.reason_code(),
+        "BREAKER_SIM_FAILED_STREAK"
+    );
+    assert_eq!(
+        TripReason::ErrorCodeBurst.reason_code(),
+        "BREAKER_ERROR_CODE_BURST"
+    );
+    assert_eq!(
+        TripReason::RealizedLossWindow.reason_code(),
+        "BREAKER_REALIZED_LOSS_WINDOW"
+    );
+    assert_eq!(
+        TripReason::ConfirmLatency.reason_code(),
+        "BREAKER_CONFIRM_LATENCY"
+    );
+
+    let mut breaker = CircuitBreaker::new(streak_only_config(1));
+    let trip = breaker
+        .record(SRC_MOMENTUM, DEX_PUMPFUN, sim_failed("Custom(1)"), 42)
+        .expect("trip");
+    let event = CircuitBreakerEvent::from_transition("execution-engine", "eval", "run-a50", &trip);
+
+    let event_json = serde_json::to_string(&event).unwrap();
+    assert!(
+        event_json.contains("\"reason_code\":\"BREAKER_SIM_FAILED_STREAK\""),
+        "Event muss reason_code tragen: {event_json}"
+    );
+    let back: CircuitBreakerEvent = serde_json::from_str(&event_json).unwrap();
+    assert_eq!(back.scope, BreakerScope::Source(SRC_MOMENTUM.to_string()));
+    assert_eq!(back.transition, BreakerTransition::Tripped);
+    assert_eq!(back.at_ms, 42);
+    assert_eq!(back.header.component, "execution-engine");
+}
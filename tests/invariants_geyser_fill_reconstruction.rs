@@ -0,0 +1,379 @@
+//! Invariante A.56: Fill-Rekonstruktion aus Geyser-Transaction-Meta (INVARIANTS.md A.56)
+//!
+//! Blackbox gegen `ironcrab::execution::fill_reconstruction` und `ironcrab::ipc`:
+//! - market-data leitet vom Wallet signierte `GeyserTransactionUpdate`s als
+//!   `OwnTransactionUpdate` auf `TOPIC_OWN_TRANSACTIONS` an die execution-engine weiter.
+//! - `fill_in`/`fill_out`, Network-Fee und CU kommen aus Pre/Post-Balances (kein RPC).
+//! - RPC bleibt Cold-Path-Fallback nach Timeout; jeder Fill traegt `fill_source`.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; kein RPC im Geyser-Fill-Pfad.
+
+use ironcrab::execution::fill_reconstruction::{
+    is_wallet_signed, reconstruct_fill, FillLegs, FillReconstructionError, PendingFills,
+};
+use ironcrab::ipc::{ExecutionResult, ExplicitAmount, FillSource, OwnTransactionUpdate};
+use ironcrab::nats::topics::TOPIC_OWN_TRANSACTIONS;
+use ironcrab::solana::geyser_listener::{GeyserTransactionUpdate, TokenAmount, TokenBalance};
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const FEE: u64 = 5_000;
+const ATA_RENT: u64 = 2_039_280;
+
+fn wsol() -> Pubkey {
+    Pubkey::from_str(WSOL_MINT).unwrap()
+}
+
+fn token_balance(
+    account_index: u8,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> TokenBalance {
+    TokenBalance {
+        account_index,
+        mint: mint.to_string(),
+        ui_token_amount: TokenAmount {
+            ui_amount: Some(amount as f64 / 10f64.powi(decimals as i32)),
+            decimals,
+            amount: amount.to_string(),
+        },
+        program_id: Some("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string()),
+        owner: Some(owner.to_string()),
+    }
+}
+
+/// Account-Layout: [0] Wallet (Fee Payer), [1] Pool-Vault (Token), [2] Wallet-Token-ATA,
+/// [3] Wallet-WSOL-ATA, [4] Pool-Authority.
+fn update(
+    wallet: Pubkey,
+    pre_balances: Vec<u64>,
+    post_balances: Vec<u64>,
+    pre_token_balances: Vec<TokenBalance>,
+    post_token_balances: Vec<TokenBalance>,
+) -> GeyserTransactionUpdate {
+    GeyserTransactionUpdate {
+        signature: "own_sig_1".to_string(),
+        slot: 777,
+        account_keys: vec![
+            wallet,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ],
+        instruction_accounts: vec![],
+        instruction_data: vec![],
+        inner_instructions: vec![],
+        pre_token_balances,
+        post_token_balances,
+        pre_balances,
+        post_balances,
+        fee_lamports: FEE,
+        compute_units_consumed: Some(85_000),
+        grpc_recv_at: Instant::now(),
+    }
+}
+
+/// BUY 0.1 SOL → 50 Token (6 Decimals), Token-ATA wird in der TX angelegt (Rent).
+fn buy_update(wallet: Pubkey, mint: Pubkey, pool_authority: Pubkey) -> GeyserTransactionUpdate {
+    let pre_native = 10_000_000_000;
+    update(
+        wallet,
+        vec![pre_native, 2_039_280, 0, 0, 0],
+        vec![
+            pre_native - 100_000_000 - FEE - ATA_RENT,
+            2_039_280,
+            ATA_RENT,
+            0,
+            0,
+        ],
+        vec![token_balance(1, &mint, &pool_authority, 900_000_000, 6)],
+        vec![
+            token_balance(1, &mint, &pool_authority, 850_000_000, 6),
+            token_balance(2, &mint, &wallet, 50_000_000, 6),
+        ],
+    )
+}
+
+fn buy_legs(wallet: Pubkey, mint: Pubkey) -> FillLegs {
+    FillLegs {
+        wallet,
+        input_mint: wsol(),
+        output_mint: mint,
+    }
+}
+
+fn sell_legs(wallet: Pubkey, mint: Pubkey) -> FillLegs {
+    FillLegs {
+        wallet,
+        input_mint: mint,
+        output_mint: wsol(),
+    }
+}
+
+fn sent_result() -> ExecutionResult {
+    ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-a56",
+        "exe-a56".to_string(),
+        "dec-a56".to_string(),
+        "intent-a56".to_string(),
+        "momentum-bot".to_string(),
+        Some(Pubkey::new_unique().to_string()),
+        Some("own_sig_1".to_string()),
+        None,
+    )
+}
+
+/// Nur TXs mit Wallet als Fee Payer (account_keys[0]) gelten als eigene TX.
+#[test]
+fn a56_wallet_signed_filter_uses_fee_payer() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let own = buy_update(wallet, mint, Pubkey::new_unique());
+    assert!(is_wallet_signed(&own, &wallet));
+
+    let mut foreign = buy_update(Pubkey::new_unique(), mint, Pubkey::new_unique());
+    // Wallet taucht als Nicht-Signer-Account auf (z. B. Transfer an uns) → keine eigene TX.
+    foreign.account_keys[4] = wallet;
+    assert!(!is_wallet_signed(&foreign, &wallet));
+}
+
+/// BUY: fill_in = SOL-Ausgabe ohne Network-Fee und ohne ATA-Rent; fill_out = Token-Delta.
+#[test]
+fn a56_buy_fill_from_pre_post_balances() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let own = OwnTransactionUpdate::from_geyser(&buy_update(wallet, mint, Pubkey::new_unique()));
+
+    let fill = reconstruct_fill(&own, &buy_legs(wallet, mint)).unwrap();
+    assert_eq!(fill.signature, "own_sig_1");
+    assert_eq!(fill.slot, 777);
+    assert_eq!(fill.fill_in, ExplicitAmount::new(100_000_000, 9));
+    assert_eq!(fill.fill_out, ExplicitAmount::new(50_000_000, 6));
+    assert_eq!(fill.network_fee_lamports, FEE);
+    assert_eq!(fill.compute_units, Some(85_000));
+}
+
+/// SELL: fill_in = Token-Abgang, fill_out = nativer SOL-Zugang zzgl. Fee.
+#[test]
+fn a56_sell_fill_native_sol_output() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let u = update(
+        wallet,
+        vec![1_000_000_000, 2_039_280, ATA_RENT, 0, 0],
+        vec![1_000_000_000 + 90_000_000 - FEE, 2_039_280, ATA_RENT, 0, 0],
+        vec![
+            token_balance(1, &mint, &authority, 850_000_000, 6),
+            token_balance(2, &mint, &wallet, 50_000_000, 6),
+        ],
+        vec![
+            token_balance(1, &mint, &authority, 900_000_000, 6),
+            token_balance(2, &mint, &wallet, 0, 6),
+        ],
+    );
+    let fill = reconstruct_fill(
+        &OwnTransactionUpdate::from_geyser(&u),
+        &sell_legs(wallet, mint),
+    )
+    .unwrap();
+    assert_eq!(fill.fill_in, ExplicitAmount::new(50_000_000, 6));
+    assert_eq!(fill.fill_out, ExplicitAmount::new(90_000_000, 9));
+}
+
+/// SELL in ein bestehendes WSOL-ATA: WSOL-Token-Delta zaehlt zum SOL-Leg.
+#[test]
+fn a56_sell_fill_into_wsol_account() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let u = update(
+        wallet,
+        vec![1_000_000_000, 0, ATA_RENT, ATA_RENT, 0],
+        vec![1_000_000_000 - FEE, 0, ATA_RENT, ATA_RENT + 90_000_000, 0],
+        vec![
+            token_balance(2, &mint, &wallet, 50_000_000, 6),
+            token_balance(3, &wsol(), &wallet, 0, 9),
+        ],
+        vec![
+            token_balance(2, &mint, &wallet, 0, 6),
+            token_balance(3, &wsol(), &wallet, 90_000_000, 9),
+        ],
+    );
+    let fill = reconstruct_fill(
+        &OwnTransactionUpdate::from_geyser(&u),
+        &sell_legs(wallet, mint),
+    )
+    .unwrap();
+    assert_eq!(fill.fill_in, ExplicitAmount::new(50_000_000, 6));
+    assert_eq!(fill.fill_out, ExplicitAmount::new(90_000_000, 9));
+}
+
+/// Fremde Token-Accounts desselben Mints (Pool-Vault) zaehlen nicht; fehlt ein Wallet-Delta
+/// auf einem Leg → Fehler statt geratenem Fill.
+#[test]
+fn a56_missing_wallet_delta_is_error() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let u = update(
+        wallet,
+        vec![1_000_000_000, 0, 0, 0, 0],
+        vec![1_000_000_000 - FEE, 0, 0, 0, 0],
+        vec![token_balance(1, &mint, &authority, 900_000_000, 6)],
+        vec![token_balance(1, &mint, &authority, 850_000_000, 6)],
+    );
+    let err = reconstruct_fill(
+        &OwnTransactionUpdate::from_geyser(&u),
+        &buy_legs(wallet, mint),
+    )
+    .unwrap_err();
+    assert!(matches!(err, FillReconstructionError::NoWalletDelta { .. }));
+
+    let foreign = buy_update(Pubkey::new_unique(), mint, authority);
+    let err = reconstruct_fill(
+        &OwnTransactionUpdate::from_geyser(&foreign),
+        &buy_legs(wallet, mint),
+    )
+    .unwrap_err();
+    assert_eq!(err, FillReconstructionError::NotWalletSigned);
+}
+
+/// Wire-Typ: `OwnTransactionUpdate` ist JSON-roundtrip-faehig und liefert nach Roundtrip
+/// denselben Fill; Topic-Name ist stabil.
+#[test]
+fn a56_own_transaction_update_roundtrip() {
+    assert_eq!(TOPIC_OWN_TRANSACTIONS, "ironcrab.v1.own_transactions");
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let own = OwnTransactionUpdate::from_geyser(&buy_update(wallet, mint, Pubkey::new_unique()));
+    let json = serde_json::to_string(&own).unwrap();
+    let parsed: OwnTransactionUpdate = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, own);
+    assert_eq!(
+        reconstruct_fill(&parsed, &buy_legs(wallet, mint)).unwrap(),
+        reconstruct_fill(&own, &buy_legs(wallet, mint)).unwrap()
+    );
+}
+
+/// `apply_to` setzt Fills, Network-Fee, CU und labelt die Quelle `geyser`.
+#[test]
+fn a56_fill_source_labeled_on_execution_result() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let own = OwnTransactionUpdate::from_geyser(&buy_update(wallet, mint, Pubkey::new_unique()));
+    let fill = reconstruct_fill(&own, &buy_legs(wallet, mint)).unwrap();
+
+    let result = fill.apply_to(sent_result());
+    assert_eq!(result.fill_source, Some(FillSource::Geyser));
+    assert_eq!(result.fill_in, Some(ExplicitAmount::new(100_000_000, 9)));
+    assert_eq!(result.fill_out, Some(ExplicitAmount::new(50_000_000, 6)));
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["fill_source"], "geyser");
+    assert_eq!(json["fees"]["network_fee_lamports"], FEE);
+    assert_eq!(json["fees"]["compute_units"], 85_000);
+
+    let rpc = sent_result().with_fill_source(FillSource::Rpc);
+    assert_eq!(serde_json::to_value(&rpc).unwrap()["fill_source"], "rpc");
+
+    // Alte Records ohne Feld bleiben lesbar (additiv, STORAGE_CONVENTIONS §4.4).
+    let mut legacy = serde_json::to_value(sent_result()).unwrap();
+    legacy.as_object_mut().unwrap().remove("fill_source");
+    let parsed: ExecutionResult = serde_json::from_value(legacy).unwrap();
+    assert_eq!(parsed.fill_source, None);
+}
+
+/// Pending-Fills: Match per Signatur genau einmal; nach Timeout → RPC-Fallback-Liste;
+/// spaetes Geyser-Update nach Fallback wird ignoriert (kein doppelter Fill).
+#[test]
+fn a56_pending_fills_match_once_and_fall_back_after_timeout() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut pending = PendingFills::new(2_000);
+    pending.register("own_sig_1".to_string(), buy_legs(wallet, mint), 10_000);
+    pending.register("sig_slow".to_string(), buy_legs(wallet, mint), 10_000);
+    assert_eq!(pending.len(), 2);
+
+    assert_eq!(pending.take("unknown_sig"), None);
+    assert_eq!(pending.take("own_sig_1"), Some(buy_legs(wallet, mint)));
+    assert_eq!(
+        pending.take("own_sig_1"),
+        None,
+        "nur ein Match pro Signatur"
+    );
+
+    assert!(
+        pending.expired(11_999).is_empty(),
+        "vor Timeout kein Fallback"
+    );
+    let expired = pending.expired(12_000);
+    assert_eq!(
+        expired,
+        vec![("sig_slow".to_string(), buy_legs(wallet, mint))]
+    );
+    assert_eq!(
+        pending.take("sig_slow"),
+        None,
+        "nach RPC-Fallback kein Geyser-Fill mehr"
+    );
+    assert_eq!(pending.len(), 0);
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: market-data publisht eigene TXs, execution-engine konsumiert sie;
+/// RPC-Fill-Fetch bleibt nur im Fallback-Pfad.
+#[test]
+fn a56_topic_wiring_market_data_to_execution_engine() {
+    let md = iron_crab_bin_rs("market_data");
+    let ee = iron_crab_bin_rs("execution_engine");
+    if !md.is_file() || !ee.is_file() {
+        eprintln!("SKIP: Iron_crab Sibling-Checkout fehlt ({:?})", md);
+        return;
+    }
+    let read_prod = |p: &PathBuf| {
+        let src = fs::read_to_string(p).unwrap_or_else(|e| panic!("read {p:?}: {e}"));
+        src.split("#[cfg(test)]")
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    };
+    let md_src = read_prod(&md);
+    let ee_src = read_prod(&ee);
+    if !md_src.contains("TOPIC_OWN_TRANSACTIONS") {
+        eprintln!(
+            "SKIP: TOPIC_OWN_TRANSACTIONS noch nicht verdrahtet in {:?}",
+            md
+        );
+        return;
+    }
+    assert!(
+        md_src.contains("is_wallet_signed"),
+        "market_data.rs muss eigene TXs via is_wallet_signed filtern"
+    );
+    assert!(
+        ee_src.contains("TOPIC_OWN_TRANSACTIONS") && ee_src.contains("PendingFills"),
+        "execution_engine.rs muss TOPIC_OWN_TRANSACTIONS konsumieren und PendingFills nutzen"
+    );
+    assert!(
+        !md_src.contains("FillSource::"),
+        "market_data.rs rekonstruiert keine Fills (nur Weiterleitung)"
+    );
+}
@@ -0,0 +1,320 @@
+//! Invariante A.63: Bus-Abstraktion mit NATS- und In-Process-Backend (INVARIANTS.md A.63)
+//!
+//! Blackbox gegen `ironcrab::nats::bus`:
+//! - Ein `Bus`-Trait ueber die Subjects aus `nats::topics`; Backends `NatsBus` und
+//!   `InProcessBus` (tokio-Channels) mit gleicher Subject-Semantik (exakt, `*`, `>`).
+//! - In-Process: Fan-out an alle Subscriber, FIFO pro Subject, Slow-Consumer-Drop ohne
+//!   Blockieren des Publishers (wie Core-NATS, I-4b).
+//! - NATS: Live-Test gegen lokalen `nats-server` (SKIP ohne Binary) mit derselben
+//!   Subject-Semantik und denselben Payload-Bytes wie ein direkter `async_nats`-Client.
+//! - Rollen-Grenzen bleiben im Code erzwungen: `RoleScopedBus` laesst nur die Publishes der
+//!   Prozess-Zugriffsmatrix zu (Intent-Only, I-2), inkl. Cancel/Replace/Conditional fuer
+//!   Strategien; nur `ExecutionEngine` darf Keys halten (I-1).
+//! - Plan K Phase 2: kombiniertes Binary (market-data + arb-strategy + execution-engine) mit
+//!   geteiltem `LivePoolCache`.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Single Signer bleibt unverletzt.
+
+mod common;
+
+use bytes::Bytes;
+use common::request_reply_e2e_harness::RequestReplyE2eHarness;
+use futures::StreamExt;
+use ironcrab::nats::bus::{
+    subject_matches, Bus, BusError, ComponentRole, InProcessBus, NatsBus, RoleScopedBus,
+};
+use ironcrab::nats::topics::{
+    intent_ack_subject, TOPIC_CONDITIONAL_INTENTS, TOPIC_DECISION_RECORDS, TOPIC_EXECUTION_RESULTS,
+    TOPIC_INTENT_CANCELS, TOPIC_INTENT_REPLACES, TOPIC_MARKET_EVENTS, TOPIC_TRADE_INTENTS,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Subject-Matching wie NATS: `*` = genau ein Token, `>` = ein oder mehr Tokens am Ende.
+#[test]
+fn a63_subject_matching_follows_nats_rules() {
+    assert!(subject_matches(TOPIC_TRADE_INTENTS, TOPIC_TRADE_INTENTS));
+    assert!(!subject_matches(TOPIC_TRADE_INTENTS, TOPIC_INTENT_CANCELS));
+    assert!(subject_matches(
+        "ironcrab.v1.intent_acks.*",
+        &intent_ack_subject("momentum-bot")
+    ));
+    assert!(!subject_matches(
+        "ironcrab.v1.intent_acks.*",
+        "ironcrab.v1.intent_acks.a.b"
+    ));
+    assert!(subject_matches(
+        "ironcrab.lot_ledger.>",
+        "ironcrab.lot_ledger.MintA"
+    ));
+    assert!(!subject_matches(
+        "ironcrab.lot_ledger.>",
+        "ironcrab.lot_ledger"
+    ));
+}
+
+/// Fan-out an alle Subscriber, FIFO pro Subject, exakte Subjects isoliert.
+#[tokio::test]
+async fn a63_in_process_bus_fans_out_in_order() {
+    let bus = InProcessBus::new(16);
+    let mut a = bus.subscribe(TOPIC_TRADE_INTENTS).await.unwrap();
+    let mut b = bus.subscribe(TOPIC_TRADE_INTENTS).await.unwrap();
+    let mut cancels = bus.subscribe(TOPIC_INTENT_CANCELS).await.unwrap();
+
+    for i in 0..3u8 {
+        bus.publish(TOPIC_TRADE_INTENTS, Bytes::from(vec![i]))
+            .await
+            .unwrap();
+    }
+    bus.publish(TOPIC_INTENT_CANCELS, Bytes::from_static(b"c"))
+        .await
+        .unwrap();
+
+    for sub in [&mut a, &mut b] {
+        for i in 0..3u8 {
+            let msg = sub.next().await.expect("message");
+            assert_eq!(msg.subject, TOPIC_TRADE_INTENTS);
+            assert_eq!(msg.payload, Bytes::from(vec![i]));
+        }
+    }
+    let msg = cancels.next().await.expect("cancel");
+    assert_eq!(msg.subject, TOPIC_INTENT_CANCELS);
+    assert_eq!(msg.payload, Bytes::from_static(b"c"));
+}
+
+/// Wildcard-Subscriptions erhalten das konkrete Subject.
+#[tokio::test]
+async fn a63_in_process_bus_wildcard_subscription() {
+    let bus = InProcessBus::new(16);
+    let mut acks = bus.subscribe("ironcrab.v1.intent_acks.*").await.unwrap();
+    let subject = intent_ack_subject("arb-strategy");
+    bus.publish(&subject, Bytes::from_static(b"ack"))
+        .await
+        .unwrap();
+    assert_eq!(acks.next().await.unwrap().subject, subject);
+}
+
+/// Slow Consumer: voller Puffer → Drop + Zaehler, Publisher blockiert nicht.
+#[tokio::test]
+async fn a63_slow_consumer_is_dropped_without_blocking_publisher() {
+    let bus = InProcessBus::new(2);
+    let mut slow = bus.subscribe(TOPIC_MARKET_EVENTS).await.unwrap();
+    let started = Instant::now();
+    for i in 0..10u8 {
+        bus.publish(TOPIC_MARKET_EVENTS, Bytes::from(vec![i]))
+            .await
+            .unwrap();
+    }
+    assert!(
+        started.elapsed().as_millis() < 200,
+        "Publish darf auf vollem Puffer nicht warten"
+    );
+    assert_eq!(bus.dropped_messages(), 8);
+    assert_eq!(slow.next().await.unwrap().payload, Bytes::from(vec![0u8]));
+    assert_eq!(slow.next().await.unwrap().payload, Bytes::from(vec![1u8]));
+}
+
+/// Publish ohne Subscriber ist kein Fehler (wie Core-NATS).
+#[tokio::test]
+async fn a63_publish_without_subscriber_is_ok() {
+    let bus = InProcessBus::new(4);
+    assert!(bus
+        .publish(TOPIC_DECISION_RECORDS, Bytes::from_static(b"{}"))
+        .await
+        .is_ok());
+    assert_eq!(bus.dropped_messages(), 0);
+}
+
+/// Prozess-Zugriffsmatrix im Code: Strategien publizieren nur Intents, Engine nur Ergebnisse.
+#[tokio::test]
+async fn a63_role_scoped_bus_enforces_publish_matrix() {
+    let inner: Arc<dyn Bus> = Arc::new(InProcessBus::new(16));
+    let arb = RoleScopedBus::new(inner.clone(), ComponentRole::ArbStrategy);
+    let md = RoleScopedBus::new(inner.clone(), ComponentRole::MarketData);
+    let ee = RoleScopedBus::new(inner.clone(), ComponentRole::ExecutionEngine);
+
+    assert!(arb
+        .publish(TOPIC_TRADE_INTENTS, Bytes::from_static(b"i"))
+        .await
+        .is_ok());
+    assert!(matches!(
+        arb.publish(TOPIC_EXECUTION_RESULTS, Bytes::from_static(b"r"))
+            .await,
+        Err(BusError::NotPermitted { .. })
+    ));
+    assert!(matches!(
+        md.publish(TOPIC_TRADE_INTENTS, Bytes::from_static(b"i"))
+            .await,
+        Err(BusError::NotPermitted { .. })
+    ));
+    assert!(md
+        .publish(TOPIC_MARKET_EVENTS, Bytes::from_static(b"e"))
+        .await
+        .is_ok());
+    assert!(ee
+        .publish(TOPIC_EXECUTION_RESULTS, Bytes::from_static(b"r"))
+        .await
+        .is_ok());
+    assert!(matches!(
+        ee.publish(TOPIC_TRADE_INTENTS, Bytes::from_static(b"i"))
+            .await,
+        Err(BusError::NotPermitted { .. })
+    ));
+}
+
+/// Revocation- und Conditional-Topics (A.60, A.59) gehoeren zum Intent-Pfad der Strategien;
+/// market-data und execution-engine duerfen sie nicht publizieren.
+#[tokio::test]
+async fn a63_role_scoped_bus_scopes_cancel_replace_and_conditional_topics() {
+    let inner: Arc<dyn Bus> = Arc::new(InProcessBus::new(16));
+    for role in [ComponentRole::ArbStrategy, ComponentRole::MomentumBot] {
+        let bus = RoleScopedBus::new(inner.clone(), role);
+        for topic in [
+            TOPIC_INTENT_CANCELS,
+            TOPIC_INTENT_REPLACES,
+            TOPIC_CONDITIONAL_INTENTS,
+        ] {
+            assert!(role.may_publish(topic), "{role:?} muss {topic} publizieren");
+            assert!(
+                bus.publish(topic, Bytes::from_static(b"{}")).await.is_ok(),
+                "{role:?} → {topic}"
+            );
+        }
+    }
+    for role in [ComponentRole::MarketData, ComponentRole::ExecutionEngine] {
+        let bus = RoleScopedBus::new(inner.clone(), role);
+        for topic in [
+            TOPIC_INTENT_CANCELS,
+            TOPIC_INTENT_REPLACES,
+            TOPIC_CONDITIONAL_INTENTS,
+        ] {
+            assert!(!role.may_publish(topic), "{role:?} darf {topic} nicht");
+            assert!(
+                matches!(
+                    bus.publish(topic, Bytes::from_static(b"{}")).await,
+                    Err(BusError::NotPermitted { .. })
+                ),
+                "{role:?} → {topic} muss NotPermitted liefern"
+            );
+        }
+    }
+}
+
+/// Live gegen `nats-server`: `NatsBus` liefert exakte und Wildcard-Subjects, haelt FIFO und
+/// sendet dieselben Bytes wie ein direkter `async_nats`-Publish; `RoleScopedBus` greift auch hier.
+/// Benoetigt nats-server im PATH — sonst Skip.
+#[test]
+fn a63_nats_bus_matches_in_process_semantics() {
+    let mut harness = RequestReplyE2eHarness::new().expect("harness new");
+    if let Err(e) = harness.start_nats() {
+        if e.contains("nats-server nicht gefunden") {
+            eprintln!("SKIP: {}", e);
+            return;
+        }
+        panic!("nats start: {}", e);
+    }
+    let nats_url = harness.nats_url().to_string();
+
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    rt.block_on(async {
+        let client = async_nats::connect(&nats_url).await.unwrap();
+        let bus = NatsBus::new(client.clone());
+        let mut intents = bus.subscribe(TOPIC_TRADE_INTENTS).await.unwrap();
+        let mut acks = bus.subscribe("ironcrab.v1.intent_acks.*").await.unwrap();
+        let mut raw = client.subscribe(TOPIC_TRADE_INTENTS).await.unwrap();
+        client.flush().await.unwrap();
+
+        for i in 0..3u8 {
+            bus.publish(TOPIC_TRADE_INTENTS, Bytes::from(vec![i]))
+                .await
+                .unwrap();
+        }
+        bus.publish(TOPIC_INTENT_CANCELS, Bytes::from_static(b"c"))
+            .await
+            .unwrap();
+        let ack_subject = intent_ack_subject("arb-strategy");
+        bus.publish(&ack_subject, Bytes::from_static(b"ack"))
+            .await
+            .unwrap();
+
+        for i in 0..3u8 {
+            let msg = intents.next().await.expect("message");
+            assert_eq!(msg.subject, TOPIC_TRADE_INTENTS);
+            assert_eq!(msg.payload, Bytes::from(vec![i]));
+            let raw_msg = raw.next().await.expect("raw message");
+            assert_eq!(raw_msg.payload, msg.payload, "gleiche Bytes wie auf NATS");
+        }
+        let ack = acks.next().await.expect("ack");
+        assert_eq!(ack.subject, ack_subject);
+        assert_eq!(ack.payload, Bytes::from_static(b"ack"));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), intents.next())
+                .await
+                .is_err(),
+            "Cancel-Subject darf nicht im exakten Intent-Subject landen"
+        );
+
+        let inner: Arc<dyn Bus> = Arc::new(bus);
+        let md = RoleScopedBus::new(inner, ComponentRole::MarketData);
+        assert!(matches!(
+            md.publish(TOPIC_TRADE_INTENTS, Bytes::from_static(b"i"))
+                .await,
+            Err(BusError::NotPermitted { .. })
+        ));
+    });
+    harness.stop();
+}
+
+/// Single Signer (I-1): nur die execution-engine-Rolle darf Keys halten.
+#[test]
+fn a63_only_execution_engine_may_hold_keys() {
+    assert!(ComponentRole::ExecutionEngine.may_hold_keys());
+    for role in [
+        ComponentRole::MarketData,
+        ComponentRole::ArbStrategy,
+        ComponentRole::MomentumBot,
+        ComponentRole::PositionManager,
+    ] {
+        assert!(!role.may_hold_keys(), "{role:?} muss keyless bleiben");
+        assert!(
+            !role.may_publish(TOPIC_EXECUTION_RESULTS),
+            "{role:?} darf keine ExecutionResults publizieren"
+        );
+    }
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: kombiniertes Binary nutzt In-Process-Bus, geteilten Cache und
+/// rollen-gescopte Busse fuer alle drei Komponenten.
+#[test]
+fn a63_single_process_binary_wires_roles() {
+    let bin = iron_crab_bin_rs("single_process");
+    let Ok(src) = fs::read_to_string(&bin) else {
+        eprintln!("SKIP: single_process.rs noch nicht vorhanden ({:?})", bin);
+        return;
+    };
+    for needle in [
+        "InProcessBus",
+        "LivePoolCache",
+        "RoleScopedBus",
+        "ComponentRole::MarketData",
+        "ComponentRole::ArbStrategy",
+        "ComponentRole::ExecutionEngine",
+    ] {
+        assert!(
+            src.contains(needle),
+            "single_process.rs muss `{needle}` nutzen"
+        );
+    }
+}
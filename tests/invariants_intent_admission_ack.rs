@@ -0,0 +1,326 @@
+//! Invariante A.61: Intent-Admission-Acks an Strategien (INVARIANTS.md A.61)
+//!
+//! Blackbox gegen `ironcrab::execution::intent_ack` und `ironcrab::ipc::IntentAck`:
+//! - Die execution-engine schickt nach den Admission-Gates einen kompakten Ack: admitted/rejected,
+//!   primaerer Reason-Code, Queue-Position.
+//! - Jedes finale Outcome (auch Risk- und Simulations-Rejects nach der Admission) erzeugt genau
+//!   einen finalen Ack aus dem DecisionRecord.
+//! - Zustellung auf `ironcrab.v1.intent_acks.{source}`; bei NATS-Request zusaetzlich als Reply.
+//! - Ack-Status folgt den Admission-Checks (gleiche `CheckResult`s wie im DecisionRecord).
+//! - Zustell-Latenz = Ack-Zeitstempel − Intent-Zeitstempel (Eval-Harness misst darauf).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Ack ersetzt keinen DecisionRecord (I-11).
+
+use ironcrab::execution::intent_ack::{ack_targets, ADMISSION_CHECK_NAMES};
+use ironcrab::ipc::{
+    AckStatus, CheckResult, DecisionOutcome, DecisionRecord, ExplicitAmount, IntentAck,
+    IntentOrigin, IntentTier, SimulationResult, TradeIntent, TradeResources, TradeSide,
+    TradingRegime,
+};
+use ironcrab::nats::topics::{intent_ack_subject, TOPIC_INTENT_ACKS};
+use std::fs;
+use std::path::PathBuf;
+
+const MINT: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const POOL: &str = "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP";
+
+fn intent(intent_id: &str, source: &str) -> TradeIntent {
+    TradeIntent::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a61",
+        intent_id.to_string(),
+        source,
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(10_000_000, 9),
+        TradeResources {
+            input_mint: WSOL_MINT.to_string(),
+            output_mint: MINT.to_string(),
+            pools: vec![POOL.to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    )
+}
+
+fn check(name: &str, passed: bool, reason: Option<&str>) -> CheckResult {
+    CheckResult {
+        check_name: name.to_string(),
+        passed,
+        reason_code: reason.map(str::to_string),
+        details: None,
+    }
+}
+
+fn all_passed() -> Vec<CheckResult> {
+    ADMISSION_CHECK_NAMES
+        .iter()
+        .map(|n| check(n, true, None))
+        .collect()
+}
+
+/// Admission-Gates sind die billigen Intake-Checks in Pipeline-Reihenfolge.
+#[test]
+fn a61_admission_checks_are_intake_gates_in_pipeline_order() {
+    assert_eq!(
+        ADMISSION_CHECK_NAMES,
+        &[
+            "idempotency",
+            "ttl_valid",
+            "kill_switch",
+            "circuit_breaker",
+            "intent_revocation"
+        ]
+    );
+}
+
+/// Alle Gates passed → `Admitted` mit Queue-Position, kein Reason-Code.
+#[test]
+fn a61_admitted_ack_carries_queue_position() {
+    let i = intent("int-1", "momentum-bot");
+    let ack = IntentAck::from_admission_checks(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        &i,
+        &all_passed(),
+        3,
+    );
+    assert_eq!(ack.intent_id, "int-1");
+    assert_eq!(ack.source, "momentum-bot");
+    assert_eq!(ack.status, AckStatus::Admitted);
+    assert_eq!(ack.queue_position, Some(3));
+    assert!(ack.primary_reason_code.is_none());
+    assert_eq!(ack.intent_ts_unix_ms, i.header.ts_unix_ms);
+    assert!(ack.outcome.is_none());
+    assert!(
+        !ack.is_final(),
+        "Admitted ist vorlaeufig, das finale Outcome folgt"
+    );
+}
+
+/// Erster failed Check bestimmt den primaeren Reason-Code (wie `primary_reject_reason`).
+#[test]
+fn a61_rejected_ack_uses_first_failed_reason_code() {
+    let i = intent("int-1", "momentum-bot");
+    let checks = vec![
+        check("idempotency", true, None),
+        check("ttl_valid", true, None),
+        check("kill_switch", false, Some("KILL_SWITCH_ACTIVE")),
+        check("circuit_breaker", false, Some("CIRCUIT_BREAKER_OPEN")),
+    ];
+    let ack =
+        IntentAck::from_admission_checks("execution-engine", "v0.1.0", "run-a61", &i, &checks, 7);
+    assert_eq!(ack.status, AckStatus::Rejected);
+    assert_eq!(
+        ack.primary_reason_code.as_deref(),
+        Some("KILL_SWITCH_ACTIVE")
+    );
+    assert!(
+        ack.queue_position.is_none(),
+        "rejected → keine Queue-Position"
+    );
+    assert!(ack.is_final(), "Intake-Reject ist selbst der finale Ack");
+}
+
+/// Risk-Gate-Reject nach erfolgreicher Admission: zweiter, finaler Ack aus dem DecisionRecord.
+#[test]
+fn a61_risk_gate_rejection_sends_final_ack() {
+    let i = intent("int-risk", "momentum-bot");
+    let admitted = IntentAck::from_admission_checks(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        &i,
+        &all_passed(),
+        0,
+    );
+    assert_eq!(admitted.status, AckStatus::Admitted);
+    assert!(!admitted.is_final());
+
+    let mut checks = all_passed();
+    checks.push(check("risk_max_position", false, Some("RISK_MAX_POSITION")));
+    let record = DecisionRecord::new_rejected(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        "dec-risk".to_string(),
+        i.intent_id.clone(),
+        i.source.clone(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        checks,
+        "RISK_MAX_POSITION".to_string(),
+    );
+    let ack = IntentAck::from_decision("execution-engine", "v0.1.0", "run-a61", &i, &record);
+    assert_eq!(ack.intent_id, "int-risk");
+    assert_eq!(ack.source, "momentum-bot");
+    assert_eq!(ack.status, AckStatus::Rejected);
+    assert_eq!(ack.outcome, Some(DecisionOutcome::Rejected));
+    assert!(ack.is_final());
+    assert!(ack.queue_position.is_none());
+    assert_eq!(
+        ack.primary_reason_code.as_deref(),
+        Some("RISK_MAX_POSITION")
+    );
+    let record_json = serde_json::to_value(&record).unwrap();
+    assert_eq!(
+        record_json["primary_reject_reason"],
+        serde_json::json!(ack.primary_reason_code),
+        "Ack-Reason == primary_reject_reason im DecisionRecord"
+    );
+    assert_eq!(ack.intent_ts_unix_ms, i.header.ts_unix_ms);
+}
+
+/// SimFailed ist ebenfalls ein finales Reject-Outcome mit Reason-Code des failed Checks.
+#[test]
+fn a61_sim_failed_sends_final_rejected_ack() {
+    let i = intent("int-sim", "arb-strategy");
+    let mut checks = all_passed();
+    checks.push(check("simulation", false, Some("SIM_FAILED")));
+    let record = DecisionRecord::new_sim_failed(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        "dec-sim".to_string(),
+        i.intent_id.clone(),
+        i.source.clone(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        checks,
+        "plan-hash-a61".to_string(),
+        SimulationResult {
+            success: false,
+            error_code: Some("InstructionError".to_string()),
+            logs_preview: None,
+            compute_units_consumed: Some(50_000),
+        },
+    );
+    let ack = IntentAck::from_decision("execution-engine", "v0.1.0", "run-a61", &i, &record);
+    assert_eq!(ack.status, AckStatus::Rejected);
+    assert_eq!(ack.outcome, Some(DecisionOutcome::SimFailed));
+    assert_eq!(ack.primary_reason_code.as_deref(), Some("SIM_FAILED"));
+    assert!(ack.is_final());
+}
+
+/// Latenz = Ack-ts − Intent-ts, saturierend.
+#[test]
+fn a61_latency_is_ack_minus_intent_timestamp() {
+    let i = intent("int-1", "momentum-bot");
+    let mut ack = IntentAck::from_admission_checks(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        &i,
+        &all_passed(),
+        0,
+    );
+    assert!(ack.header.ts_unix_ms >= i.header.ts_unix_ms);
+    ack.header.ts_unix_ms = i.header.ts_unix_ms + 4;
+    assert_eq!(ack.latency_ms(), 4);
+    ack.header.ts_unix_ms = i.header.ts_unix_ms - 1;
+    assert_eq!(ack.latency_ms(), 0, "Clock-Skew darf nicht unterlaufen");
+}
+
+/// Routing: immer auf das Source-Subject; bei Request zusaetzlich (zuerst) an die Reply-Inbox.
+#[test]
+fn a61_ack_routing_topic_and_optional_reply() {
+    assert_eq!(TOPIC_INTENT_ACKS, "ironcrab.v1.intent_acks");
+    assert_eq!(
+        intent_ack_subject("momentum-bot"),
+        "ironcrab.v1.intent_acks.momentum-bot"
+    );
+    assert_eq!(
+        ack_targets("arb-strategy", None),
+        vec!["ironcrab.v1.intent_acks.arb-strategy".to_string()]
+    );
+    assert_eq!(
+        ack_targets("arb-strategy", Some("_INBOX.abc.1")),
+        vec![
+            "_INBOX.abc.1".to_string(),
+            "ironcrab.v1.intent_acks.arb-strategy".to_string()
+        ]
+    );
+}
+
+/// Wire: kompaktes JSON mit flachem Header, Status snake_case, optionale Felder als null.
+#[test]
+fn a61_wire_contract() {
+    let i = intent("int-1", "momentum-bot");
+    let ack = IntentAck::from_admission_checks(
+        "execution-engine",
+        "v0.1.0",
+        "run-a61",
+        &i,
+        &all_passed(),
+        2,
+    );
+    let json = serde_json::to_value(&ack).unwrap();
+    assert_eq!(json["status"], "admitted");
+    assert_eq!(json["intent_id"], "int-1");
+    assert_eq!(json["queue_position"], 2);
+    assert!(json["primary_reason_code"].is_null());
+    assert!(
+        json["outcome"].is_null(),
+        "Admission-Ack traegt kein Outcome"
+    );
+    assert_eq!(json["component"], "execution-engine");
+    let parsed: IntentAck = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, ack);
+
+    assert_eq!(
+        serde_json::to_string(&AckStatus::Rejected).unwrap(),
+        "\"rejected\""
+    );
+    assert_eq!(
+        serde_json::to_string(&AckStatus::Accepted).unwrap(),
+        "\"accepted\""
+    );
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: Engine sendet Acks, beide Strategien konsumieren ihr Source-Subject.
+#[test]
+fn a61_engine_and_strategies_wire_acks() {
+    let Ok(engine) = fs::read_to_string(iron_crab_bin_rs("execution_engine")) else {
+        eprintln!("SKIP: execution_engine.rs nicht vorhanden");
+        return;
+    };
+    if !engine.contains("IntentAck") {
+        eprintln!("SKIP: IntentAck noch nicht verdrahtet");
+        return;
+    }
+    assert!(
+        engine.contains("ack_targets"),
+        "execution_engine.rs muss Acks ueber ack_targets routen"
+    );
+    assert!(
+        engine.contains("IntentAck::from_decision"),
+        "execution_engine.rs muss fuer jedes finale Outcome einen Ack senden"
+    );
+    for bin in ["momentum_bot", "arb_strategy"] {
+        let Ok(src) = fs::read_to_string(iron_crab_bin_rs(bin)) else {
+            eprintln!("SKIP: {bin}.rs nicht vorhanden");
+            continue;
+        };
+        assert!(
+            src.contains("intent_ack_subject"),
+            "{bin}.rs muss sein Ack-Subject abonnieren"
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Invariante A.60: IntentCancel / IntentReplace bis zum Signier-Zeitpunkt (INVARIANTS.md A.60)
+//!
+//! Blackbox gegen `ironcrab::execution::intent_revocation` und `ironcrab::ipc`:
+//! - `IntentCancel` / `IntentReplace` auf der Topic-Familie `ironcrab.v1.trade_intents.*`.
+//! - Die Engine honoriert Revocations bis `begin_signing`; danach `TooLate`.
+//! - Genau ein Outcome pro Intent (I-11): widerrufene Intents enden `Rejected` mit
+//!   `CANCELLED` bzw. `SUPERSEDED`; die Ersetzung laeuft als neuer Intent durch die Pipeline.
+//! - Cancel, der den Intent ueberholt, wird als Tombstone gehalten (`Deferred`).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; kein Widerruf nach dem Signieren.
+
+use ironcrab::execution::intent_revocation::{
+    Revocation, RevocationAck, RevocationRegistry, INTENT_REVOCATION_CHECK_NAME,
+};
+use ironcrab::ipc::{
+    DecisionOutcome, DecisionRecord, ExplicitAmount, IntentCancel, IntentOrigin, IntentReplace,
+    IntentTier, RejectReason, TradeIntent, TradeResources, TradeSide, TradingRegime,
+};
+use ironcrab::nats::topics::{TOPIC_INTENT_CANCELS, TOPIC_INTENT_REPLACES, TOPIC_TRADE_INTENTS};
+use ironcrab::storage::{LockHolder, LockManager, LockResult};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const MINT: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const POOL: &str = "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP";
+const TOMBSTONE_TTL_MS: u64 = 10_000;
+const WALLET_SOL: u64 = 1_000_000_000;
+
+fn buy_intent(intent_id: &str, source: &str, raw: u64) -> TradeIntent {
+    TradeIntent::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a60",
+        intent_id.to_string(),
+        source,
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(raw, 9),
+        TradeResources {
+            input_mint: WSOL_MINT.to_string(),
+            output_mint: MINT.to_string(),
+            pools: vec![POOL.to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    )
+}
+
+fn cancel(target: &str, source: &str) -> IntentCancel {
+    IntentCancel::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a60",
+        format!("cancel-{target}"),
+        source,
+        target.to_string(),
+        Some("signal_reversed".to_string()),
+    )
+}
+
+fn replace(target: &str, replacement: TradeIntent) -> IntentReplace {
+    IntentReplace::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a60",
+        format!("replace-{target}"),
+        "momentum-bot",
+        target.to_string(),
+        replacement,
+    )
+}
+
+/// Cancel vor dem Signieren: Checkpoint und Signier-Punkt liefern `Cancelled`.
+#[test]
+fn a60_cancel_before_signing_is_honoured() {
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    let intent = buy_intent("int-1", "momentum-bot", 10_000_000);
+    assert!(reg.admit(&intent, 1_000).is_none());
+    assert!(reg.checkpoint("int-1").is_none());
+
+    assert_eq!(
+        reg.cancel(&cancel("int-1", "momentum-bot"), 1_010),
+        RevocationAck::Accepted
+    );
+    let revocation = reg
+        .checkpoint("int-1")
+        .expect("Cancel sichtbar am Checkpoint");
+    assert_eq!(
+        revocation,
+        Revocation::Cancelled {
+            cancel_id: "cancel-int-1".to_string(),
+            reason: Some("signal_reversed".to_string()),
+        }
+    );
+    assert_eq!(reg.begin_signing("int-1"), Err(revocation));
+}
+
+/// Nach `begin_signing` ist der Intent nicht mehr widerrufbar; nach `finish` ebenso.
+#[test]
+fn a60_cancel_after_signing_is_too_late() {
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    assert!(reg
+        .admit(&buy_intent("int-1", "momentum-bot", 10_000_000), 1_000)
+        .is_none());
+    assert_eq!(reg.begin_signing("int-1"), Ok(()));
+    assert_eq!(
+        reg.cancel(&cancel("int-1", "momentum-bot"), 1_010),
+        RevocationAck::TooLate
+    );
+    let late_replace = replace("int-1", buy_intent("int-1b", "momentum-bot", 5_000_000));
+    assert_eq!(
+        reg.replace(&late_replace, 1_012),
+        RevocationAck::TooLate,
+        "Replace nach begin_signing: Original laeuft weiter, Ersetzung wird verworfen"
+    );
+    assert!(reg.checkpoint("int-1").is_none());
+    assert!(reg.checkpoint("int-1b").is_none());
+
+    reg.admit(&buy_intent("int-2", "momentum-bot", 10_000_000), 1_020);
+    reg.finish("int-2");
+    assert_eq!(
+        reg.cancel(&cancel("int-2", "momentum-bot"), 1_030),
+        RevocationAck::TooLate,
+        "Outcome bereits geschrieben (I-11): kein zweites Outcome"
+    );
+}
+
+/// Nur die `source`, die den Intent publiziert hat, darf ihn widerrufen.
+#[test]
+fn a60_only_originating_source_may_revoke() {
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    reg.admit(&buy_intent("int-1", "momentum-bot", 10_000_000), 1_000);
+    assert_eq!(
+        reg.cancel(&cancel("int-1", "arb-strategy"), 1_010),
+        RevocationAck::SourceMismatch
+    );
+    assert!(reg.checkpoint("int-1").is_none());
+}
+
+/// Cancel ueberholt den Intent: Tombstone (`Deferred`), der spaete Intent wird bei
+/// `admit` sofort widerrufen. Tombstones verfallen nach `tombstone_ttl_ms`.
+#[test]
+fn a60_cancel_overtaking_intent_is_tombstoned() {
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    assert_eq!(
+        reg.cancel(&cancel("int-1", "momentum-bot"), 1_000),
+        RevocationAck::Deferred
+    );
+    assert_eq!(reg.pending_tombstones(), 1);
+    let revocation = reg.admit(&buy_intent("int-1", "momentum-bot", 10_000_000), 1_005);
+    assert!(matches!(revocation, Some(Revocation::Cancelled { .. })));
+    assert_eq!(reg.pending_tombstones(), 0);
+
+    assert_eq!(
+        reg.cancel(&cancel("int-2", "momentum-bot"), 2_000),
+        RevocationAck::Deferred
+    );
+    assert_eq!(reg.expire(2_000 + TOMBSTONE_TTL_MS - 1), 0);
+    assert_eq!(reg.expire(2_000 + TOMBSTONE_TTL_MS), 1);
+    assert!(reg
+        .admit(&buy_intent("int-2", "momentum-bot", 10_000_000), 12_500)
+        .is_none());
+}
+
+/// Replace: altes Intent → `Superseded { by_intent_id }`; die Ersetzung braucht eine neue
+/// `intent_id` und dieselbe `source`.
+#[test]
+fn a60_replace_supersedes_original() {
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    reg.admit(&buy_intent("int-1", "momentum-bot", 10_000_000), 1_000);
+
+    let same_id = replace("int-1", buy_intent("int-1", "momentum-bot", 5_000_000));
+    assert_eq!(
+        reg.replace(&same_id, 1_005),
+        RevocationAck::InvalidReplacement
+    );
+    let foreign = replace("int-1", buy_intent("int-1b", "arb-strategy", 5_000_000));
+    assert_eq!(
+        reg.replace(&foreign, 1_006),
+        RevocationAck::InvalidReplacement
+    );
+
+    let smaller = replace("int-1", buy_intent("int-1b", "momentum-bot", 5_000_000));
+    assert_eq!(reg.replace(&smaller, 1_010), RevocationAck::Accepted);
+    assert_eq!(
+        reg.checkpoint("int-1"),
+        Some(Revocation::Superseded {
+            by_intent_id: "int-1b".to_string()
+        })
+    );
+    assert_eq!(smaller.replacement.required_capital.raw, 5_000_000);
+    assert!(reg.admit(&smaller.replacement, 1_011).is_none());
+    assert!(reg.checkpoint("int-1b").is_none());
+}
+
+fn rejected_outcome(intent: &TradeIntent, revocation: &Revocation) -> DecisionRecord {
+    DecisionRecord::new_rejected(
+        "execution-engine",
+        "v0.1.0",
+        "run-a60",
+        format!("dec-{}", intent.intent_id),
+        intent.intent_id.clone(),
+        intent.source.clone(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![revocation.to_check_result()],
+        serde_json::to_value(revocation.reject_reason())
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string(),
+    )
+}
+
+/// I-11 ueber die Registry: ein widerrufener Intent (Checkpoint nach Risk bzw. `begin_signing`)
+/// wird per `finalize_revoked` genau einmal abgeschlossen, seine Locks sind danach frei, und
+/// es entsteht genau ein Rejected-Outcome mit Check `intent_revocation`.
+#[test]
+fn a60_revocation_records_single_rejected_outcome() {
+    let locks = LockManager::new(WALLET_SOL).with_fairness(5, 60, 30, false);
+    let mut reg = RevocationRegistry::new(TOMBSTONE_TTL_MS);
+    let mut outcomes: Vec<DecisionRecord> = Vec::new();
+
+    // Superseded am Checkpoint nach Risk/Locks.
+    let original = buy_intent("int-1", "momentum-bot", 10_000_000);
+    assert!(reg.admit(&original, 1_000).is_none());
+    assert!(matches!(
+        locks.try_lock_capital(LockHolder::new("int-1"), 10_000_000, HashMap::new()),
+        LockResult::Acquired
+    ));
+    assert_eq!(locks.available_sol(), WALLET_SOL - 10_000_000);
+    let smaller = replace("int-1", buy_intent("int-1b", "momentum-bot", 5_000_000));
+    assert_eq!(reg.replace(&smaller, 1_010), RevocationAck::Accepted);
+    let revocation = reg.checkpoint("int-1").expect("Replace sichtbar");
+
+    let finalized = reg.finalize_revoked("int-1", &locks);
+    assert_eq!(finalized.as_ref(), Some(&revocation));
+    outcomes.extend(finalized.map(|r| rejected_outcome(&original, &r)));
+    assert_eq!(locks.available_sol(), WALLET_SOL, "Locks freigegeben");
+    assert_eq!(locks.total_native_sol(), WALLET_SOL);
+
+    assert_eq!(
+        reg.finalize_revoked("int-1", &locks),
+        None,
+        "zweiter Abschluss erzeugt kein zweites Outcome"
+    );
+    assert!(reg.checkpoint("int-1").is_none());
+    assert_eq!(
+        reg.cancel(&cancel("int-1", "momentum-bot"), 1_020),
+        RevocationAck::TooLate
+    );
+
+    // Cancelled am Signier-Punkt.
+    let other = buy_intent("int-2", "momentum-bot", 20_000_000);
+    assert!(reg.admit(&other, 1_030).is_none());
+    assert!(matches!(
+        locks.try_lock_capital(LockHolder::new("int-2"), 20_000_000, HashMap::new()),
+        LockResult::Acquired
+    ));
+    assert_eq!(
+        reg.cancel(&cancel("int-2", "momentum-bot"), 1_040),
+        RevocationAck::Accepted
+    );
+    let at_signing = reg
+        .begin_signing("int-2")
+        .expect_err("Cancel vor dem Signieren");
+    let finalized = reg.finalize_revoked("int-2", &locks);
+    assert_eq!(finalized, Some(at_signing));
+    outcomes.extend(finalized.map(|r| rejected_outcome(&other, &r)));
+    assert_eq!(reg.finalize_revoked("int-2", &locks), None);
+    assert_eq!(locks.available_sol(), WALLET_SOL, "Locks freigegeben");
+
+    // Nicht widerrufene Intents werden nicht ueber diesen Pfad abgeschlossen.
+    assert!(reg
+        .admit(&buy_intent("int-3", "momentum-bot", 1_000_000), 1_050)
+        .is_none());
+    assert_eq!(reg.finalize_revoked("int-3", &locks), None);
+
+    let ids: Vec<&str> = outcomes.iter().map(|r| r.intent_id.as_str()).collect();
+    assert_eq!(ids, ["int-1", "int-2"], "genau ein Outcome pro Intent");
+    for (record, reason) in outcomes.iter().zip(["SUPERSEDED", "CANCELLED"]) {
+        assert_eq!(record.outcome, DecisionOutcome::Rejected);
+        assert_eq!(record.checks.len(), 1);
+        assert_eq!(record.checks[0].check_name, INTENT_REVOCATION_CHECK_NAME);
+        assert!(!record.checks[0].passed);
+        assert_eq!(record.checks[0].reason_code.as_deref(), Some(reason));
+    }
+}
+
+/// Revocation-Typen: Reason-Codes und Check-Details.
+#[test]
+fn a60_revocation_reason_codes_and_check() {
+    let cancelled = Revocation::Cancelled {
+        cancel_id: "cancel-int-1".to_string(),
+        reason: None,
+    };
+    let superseded = Revocation::Superseded {
+        by_intent_id: "int-1b".to_string(),
+    };
+    assert_eq!(cancelled.reject_reason(), RejectReason::Cancelled);
+    assert_eq!(superseded.reject_reason(), RejectReason::Superseded);
+    assert_eq!(
+        serde_json::to_string(&RejectReason::Cancelled).unwrap(),
+        "\"CANCELLED\""
+    );
+    assert_eq!(
+        serde_json::to_string(&RejectReason::Superseded).unwrap(),
+        "\"SUPERSEDED\""
+    );
+
+    let check = superseded.to_check_result();
+    assert_eq!(INTENT_REVOCATION_CHECK_NAME, "intent_revocation");
+    assert_eq!(check.check_name, INTENT_REVOCATION_CHECK_NAME);
+    assert!(!check.passed);
+    assert_eq!(check.reason_code.as_deref(), Some("SUPERSEDED"));
+    assert!(check.details.unwrap_or_default().contains("int-1b"));
+    assert_eq!(
+        cancelled.to_check_result().reason_code.as_deref(),
+        Some("CANCELLED")
+    );
+}
+
+/// Wire: Topics der trade_intents-Familie, JSON-Roundtrip mit flachem Header.
+#[test]
+fn a60_wire_contract() {
+    assert_eq!(TOPIC_TRADE_INTENTS, "ironcrab.v1.trade_intents");
+    assert_eq!(TOPIC_INTENT_CANCELS, "ironcrab.v1.trade_intents.cancel");
+    assert_eq!(TOPIC_INTENT_REPLACES, "ironcrab.v1.trade_intents.replace");
+
+    let c = cancel("int-1", "momentum-bot");
+    let json = serde_json::to_value(&c).unwrap();
+    assert_eq!(json["target_intent_id"], "int-1");
+    assert_eq!(json["source"], "momentum-bot");
+    assert!(json["ts_unix_ms"].as_u64().unwrap() > 0);
+    let parsed: IntentCancel = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, c);
+
+    let r = replace("int-1", buy_intent("int-1b", "momentum-bot", 5_000_000));
+    let json = serde_json::to_value(&r).unwrap();
+    assert_eq!(json["replacement"]["intent_id"], "int-1b");
+    let parsed: IntentReplace = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, r);
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: die execution-engine abonniert beide Topics und prueft direkt vor dem
+/// Signieren (`begin_signing`).
+#[test]
+fn a60_execution_engine_wires_revocation() {
+    let bin = iron_crab_bin_rs("execution_engine");
+    let Ok(src) = fs::read_to_string(&bin) else {
+        eprintln!("SKIP: execution_engine.rs nicht vorhanden ({:?})", bin);
+        return;
+    };
+    if !src.contains("TOPIC_INTENT_CANCELS") {
+        eprintln!("SKIP: IntentCancel noch nicht verdrahtet");
+        return;
+    }
+    assert!(
+        src.contains("TOPIC_INTENT_REPLACES"),
+        "execution_engine.rs muss auch IntentReplace abonnieren"
+    );
+    assert!(
+        src.contains("begin_signing"),
+        "execution_engine.rs muss Revocations am Signier-Punkt pruefen"
+    );
+}
@@ -0,0 +1,440 @@
+//! Invariante A.66: JSON-Schema-Export und Kompatibilitaets-Check fuer IPC-Typen (INVARIANTS.md A.66)
+//!
+//! Blackbox gegen `ironcrab::ipc::json_schema`:
+//! - Fuer jeden oeffentlichen IPC-Typ (inkl. Enums wie `RejectReason`, `ControlRequestKind`,
+//!   `MarketEventKind`) wird ein JSON-Schema aus der Rust-Definition erzeugt.
+//! - Das Schema beschreibt das tatsaechliche Wire-Format (flatten-Header, Tags, Reason-Codes).
+//! - `check_compatibility(old, new)` meldet Brueche fuer bestehende Consumer (entfernte Felder,
+//!   entfernte, umbenannte oder neue Varianten, neue Pflichtfelder, Typwechsel); nur ein neues
+//!   optionales Feld ist kompatibel.
+//! - Export und Check laufen als CLI-Subcommand (`ironcrab-cli schema export|check`).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests.
+
+use ironcrab::ipc::json_schema::{
+    check_compatibility, export_all, schema_for, SchemaBreak, IPC_SCHEMA_TYPES,
+};
+use ironcrab::ipc::{
+    ControlRequest, ControlRequestKind, DecisionRecord, IntentOrigin, MarketEvent, MarketEventKind,
+    RejectReason, TradingRegime,
+};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+/// Typen, die Python-Consumer (control-plane, trades-server) heute von Hand nachbauen.
+const REQUIRED_TYPES: [&str; 13] = [
+    "RecordHeader",
+    "ExplicitAmount",
+    "MarketEvent",
+    "MarketEventKind",
+    "TradeIntent",
+    "DecisionRecord",
+    "DecisionOutcome",
+    "ExecutionResult",
+    "RejectReason",
+    "ControlRequest",
+    "ControlRequestKind",
+    "ControlResponse",
+    "PoolCacheUpdate",
+];
+
+/// Sucht rekursiv ein `properties`-Objekt, das `key` enthaelt (auch in `$defs`, `allOf`, ...).
+fn has_property(schema: &Value, key: &str) -> bool {
+    match schema {
+        Value::Object(map) => {
+            map.get("properties")
+                .and_then(Value::as_object)
+                .is_some_and(|p| p.contains_key(key))
+                || map.values().any(|v| has_property(v, key))
+        }
+        Value::Array(items) => items.iter().any(|v| has_property(v, key)),
+        _ => false,
+    }
+}
+
+/// Sucht rekursiv einen String-Wert (z. B. in `enum`/`const`), nicht Keys.
+fn has_string_value(schema: &Value, needle: &str) -> bool {
+    match schema {
+        Value::String(s) => s == needle,
+        Value::Object(map) => map.values().any(|v| has_string_value(v, needle)),
+        Value::Array(items) => items.iter().any(|v| has_string_value(v, needle)),
+        _ => false,
+    }
+}
+
+fn schema(type_name: &str) -> Value {
+    schema_for(type_name).unwrap_or_else(|| panic!("kein Schema fuer {type_name}"))
+}
+
+/// Jeder oeffentliche IPC-Typ hat ein Schema mit Dialekt und Titel.
+#[test]
+fn a66_every_ipc_type_has_a_schema() {
+    for name in REQUIRED_TYPES {
+        assert!(
+            IPC_SCHEMA_TYPES.contains(&name),
+            "{name} fehlt in IPC_SCHEMA_TYPES"
+        );
+    }
+    let all = export_all();
+    assert_eq!(all.len(), IPC_SCHEMA_TYPES.len());
+    for name in IPC_SCHEMA_TYPES {
+        let s = all
+            .get(*name)
+            .expect("export_all deckt IPC_SCHEMA_TYPES ab");
+        assert_eq!(s, &schema(name), "{name}: export_all == schema_for");
+        assert_eq!(
+            s["$schema"], "https://json-schema.org/draft/2020-12/schema",
+            "{name}"
+        );
+        assert_eq!(s["title"], *name);
+    }
+    assert!(schema_for("NotAnIpcType").is_none());
+}
+
+/// Export ist deterministisch (diffbar im Repo).
+#[test]
+fn a66_export_is_deterministic() {
+    let a = serde_json::to_string_pretty(&export_all()).unwrap();
+    let b = serde_json::to_string_pretty(&export_all()).unwrap();
+    assert_eq!(a, b);
+}
+
+/// Das Schema beschreibt das echte Wire-Format: jedes serialisierte Top-Level-Feld
+/// (inkl. geflattetem `RecordHeader`) ist eine Schema-Property.
+#[test]
+fn a66_schema_covers_serialized_fields() {
+    let record = DecisionRecord::new_rejected(
+        "exec-engine",
+        "v0.1",
+        "run-1",
+        "dec-001".to_string(),
+        "intent-001".to_string(),
+        "momentum-bot".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![],
+        "TEST".to_string(),
+    );
+    let mut req = ControlRequest::new(
+        "ironcrab-eval",
+        "wire-test",
+        "run-1",
+        "req-001".to_string(),
+        "market-data",
+        ControlRequestKind::EnsurePumpAmmPoolAccounts {
+            base_mint: "BaseMint".to_string(),
+        },
+    );
+    req.pool_address_hint = Some("PoolHint".to_string());
+    let event = MarketEvent::new(
+        "test",
+        "v1",
+        "run",
+        "e1".to_string(),
+        "test",
+        Some(1),
+        MarketEventKind::SlotUpdate { current_slot: 1 },
+    );
+
+    for (type_name, value) in [
+        ("DecisionRecord", serde_json::to_value(&record).unwrap()),
+        ("ControlRequest", serde_json::to_value(&req).unwrap()),
+        ("MarketEvent", serde_json::to_value(&event).unwrap()),
+    ] {
+        let s = schema(type_name);
+        for key in value.as_object().unwrap().keys() {
+            assert!(
+                has_property(&s, key),
+                "{type_name}: Wire-Feld `{key}` fehlt im Schema"
+            );
+        }
+        for header_field in [
+            "schema_version",
+            "ts_unix_ms",
+            "component",
+            "build",
+            "run_id",
+        ] {
+            assert!(
+                has_property(&s, header_field),
+                "{type_name}: {header_field}"
+            );
+        }
+    }
+}
+
+/// Enums: Reason-Codes und Varianten-Namen exakt wie auf dem Draht.
+#[test]
+fn a66_enum_schemas_list_wire_variants() {
+    let reject = schema("RejectReason");
+    for reason in [
+        RejectReason::TtlExpired,
+        RejectReason::SimFailed,
+        RejectReason::Cancelled,
+    ] {
+        let wire = serde_json::to_value(reason).unwrap();
+        let code = wire.as_str().expect("RejectReason serialisiert als String");
+        assert!(has_string_value(&reject, code), "RejectReason: {code}");
+    }
+
+    let kinds = schema("MarketEventKind");
+    for variant in ["PoolCreated", "TokenMintInfo", "SlotUpdate"] {
+        assert!(
+            has_string_value(&kinds, variant) || has_property(&kinds, variant),
+            "MarketEventKind: {variant}"
+        );
+    }
+
+    let control = schema("ControlRequestKind");
+    assert!(
+        has_string_value(&control, "EnsurePumpAmmPoolAccounts")
+            || has_property(&control, "EnsurePumpAmmPoolAccounts"),
+        "ControlRequestKind: EnsurePumpAmmPoolAccounts"
+    );
+}
+
+fn object_schema(properties: Value, required: &[&str]) -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Example",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn enum_schema(variants: &[&str]) -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "ExampleReason",
+        "type": "string",
+        "enum": variants,
+    })
+}
+
+/// Liefert den Varianten-Namen eines extern getaggten Enum-Zweigs
+/// (`{"properties": {Tag: ..}, "required": [Tag]}`) oder eines Unit-Zweigs (`enum: [Tag]`).
+fn variant_tag(branch: &Value) -> Option<String> {
+    let single = |v: Option<&Value>| {
+        v.and_then(Value::as_array)
+            .filter(|a| a.len() == 1)
+            .and_then(|a| a[0].as_str())
+            .map(str::to_string)
+    };
+    single(branch.get("required")).or_else(|| single(branch.get("enum")))
+}
+
+/// Wendet `f` auf jede `oneOf`/`anyOf`-Liste im Schema an (Wurzel und `$defs`).
+fn for_each_variant_list(schema: &mut Value, f: &mut dyn FnMut(&mut Vec<Value>)) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match (key.as_str(), value) {
+                    ("oneOf" | "anyOf", Value::Array(branches)) => f(branches),
+                    (_, value) => for_each_variant_list(value, f),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| for_each_variant_list(v, f)),
+        _ => {}
+    }
+}
+
+/// `MarketEventKind`-Schema ohne die Variante `tag`.
+fn market_event_kind_without(tag: &str) -> Value {
+    let mut s = schema("MarketEventKind");
+    let mut removed = false;
+    for_each_variant_list(&mut s, &mut |branches| {
+        let before = branches.len();
+        branches.retain(|b| variant_tag(b).as_deref() != Some(tag));
+        removed |= branches.len() < before;
+    });
+    assert!(removed, "MarketEventKind: Variante {tag} nicht im Schema");
+    s
+}
+
+/// `MarketEventKind`-Schema mit umbenannter Variante `from` → `to` (Tag und Property).
+fn market_event_kind_renamed(from: &str, to: &str) -> Value {
+    let mut s = schema("MarketEventKind");
+    let mut renamed = false;
+    for_each_variant_list(&mut s, &mut |branches| {
+        for branch in branches.iter_mut() {
+            if variant_tag(branch).as_deref() != Some(from) {
+                continue;
+            }
+            branch["required"] = json!([to]);
+            let props = branch["properties"].as_object_mut().expect("properties");
+            let inner = props.remove(from).expect("Tag-Property");
+            props.insert(to.to_string(), inner);
+            renamed = true;
+        }
+    });
+    assert!(renamed, "MarketEventKind: Variante {from} nicht im Schema");
+    s
+}
+
+/// Gleiches Schema und ein neues optionales Feld sind kompatibel.
+#[test]
+fn a66_additive_changes_are_compatible() {
+    let old = object_schema(
+        json!({"id": {"type": "string"}, "qty": {"type": "integer"}}),
+        &["id"],
+    );
+    assert!(check_compatibility(&old, &old).is_empty());
+
+    let with_optional = object_schema(
+        json!({
+            "id": {"type": "string"},
+            "qty": {"type": "integer"},
+            "note": {"type": ["string", "null"]}
+        }),
+        &["id"],
+    );
+    assert!(check_compatibility(&old, &with_optional).is_empty());
+
+    let kinds = schema("MarketEventKind");
+    assert!(check_compatibility(&kinds, &kinds).is_empty());
+}
+
+/// Neue Variante bricht Consumer mit geschlossenem Match (alter Stand kann den Wert nicht lesen).
+#[test]
+fn a66_added_variant_is_breaking() {
+    let reasons = enum_schema(&["TTL_EXPIRED", "SIM_FAILED"]);
+    let more = enum_schema(&["TTL_EXPIRED", "SIM_FAILED", "CANCELLED"]);
+    let breaks = check_compatibility(&reasons, &more);
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::AddedVariant { variant, .. } if variant == "CANCELLED")
+        ),
+        "{breaks:?}"
+    );
+
+    // Getaggtes Enum: Baseline ohne SlotUpdate, aktueller Stand mit → neue Variante.
+    let old = market_event_kind_without("SlotUpdate");
+    let breaks = check_compatibility(&old, &schema("MarketEventKind"));
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::AddedVariant { variant, .. } if variant == "SlotUpdate")
+        ),
+        "{breaks:?}"
+    );
+}
+
+/// Entferntes Feld bricht Consumer.
+#[test]
+fn a66_removed_field_is_breaking() {
+    let old = object_schema(
+        json!({"id": {"type": "string"}, "qty": {"type": "integer"}}),
+        &["id"],
+    );
+    let new = object_schema(json!({"id": {"type": "string"}}), &["id"]);
+    let breaks = check_compatibility(&old, &new);
+    assert!(
+        breaks
+            .iter()
+            .any(|b| matches!(b, SchemaBreak::RemovedField { field, .. } if field == "qty")),
+        "{breaks:?}"
+    );
+}
+
+/// Umbenannte Variante = entfernte Variante (alte Werte nicht mehr lesbar).
+#[test]
+fn a66_renamed_variant_is_breaking() {
+    let old = enum_schema(&["TTL_EXPIRED", "SIM_FAILED"]);
+    let new = enum_schema(&["TTL_EXPIRED", "SIMULATION_FAILED"]);
+    let breaks = check_compatibility(&old, &new);
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::RemovedVariant { variant, .. } if variant == "SIM_FAILED")
+        ),
+        "{breaks:?}"
+    );
+}
+
+/// Getaggtes Enum (`MarketEventKind`): entfernte Variante bricht Consumer.
+#[test]
+fn a66_removed_tagged_variant_is_breaking() {
+    let new = market_event_kind_without("TokenMintInfo");
+    let breaks = check_compatibility(&schema("MarketEventKind"), &new);
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::RemovedVariant { variant, .. } if variant == "TokenMintInfo")
+        ),
+        "{breaks:?}"
+    );
+}
+
+/// Getaggtes Enum (`MarketEventKind`): Umbenennung = entfernte alte + neue Variante.
+#[test]
+fn a66_renamed_tagged_variant_is_breaking() {
+    let new = market_event_kind_renamed("PoolCreated", "PoolInitialized");
+    let breaks = check_compatibility(&schema("MarketEventKind"), &new);
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::RemovedVariant { variant, .. } if variant == "PoolCreated")
+        ),
+        "{breaks:?}"
+    );
+    assert!(
+        breaks.iter().any(
+            |b| matches!(b, SchemaBreak::AddedVariant { variant, .. } if variant == "PoolInitialized")
+        ),
+        "{breaks:?}"
+    );
+}
+
+/// Neues Pflichtfeld und Typwechsel brechen Producer/Consumer mit altem Stand.
+#[test]
+fn a66_new_required_field_and_type_change_are_breaking() {
+    let old = object_schema(
+        json!({"id": {"type": "string"}, "qty": {"type": "integer"}}),
+        &["id"],
+    );
+    let required = object_schema(
+        json!({"id": {"type": "string"}, "qty": {"type": "integer"}}),
+        &["id", "qty"],
+    );
+    assert!(check_compatibility(&old, &required)
+        .iter()
+        .any(|b| matches!(b, SchemaBreak::NewRequiredField { field, .. } if field == "qty")));
+
+    let retyped = object_schema(
+        json!({"id": {"type": "string"}, "qty": {"type": "string"}}),
+        &["id"],
+    );
+    assert!(check_compatibility(&old, &retyped)
+        .iter()
+        .any(|b| matches!(b, SchemaBreak::TypeChanged { path, .. } if path.ends_with("qty"))));
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: CLI bietet `schema export` und `schema check` auf Basis der Lib-API.
+#[test]
+fn a66_cli_exposes_schema_subcommands() {
+    let bin = iron_crab_bin_rs("ironcrab_cli");
+    let Ok(src) = fs::read_to_string(&bin) else {
+        eprintln!("SKIP: ironcrab_cli.rs noch nicht vorhanden ({:?})", bin);
+        return;
+    };
+    for needle in [
+        "export_all",
+        "check_compatibility",
+        "\"export\"",
+        "\"check\"",
+    ] {
+        assert!(
+            src.contains(needle),
+            "ironcrab_cli.rs muss `{needle}` nutzen"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Invariante A.62: Exactly-once Intent-Zustellung ueber JetStream (INVARIANTS.md A.62)
+//!
+//! Blackbox gegen `ironcrab::nats::jetstream` (Stream/Consumer/Header) und
+//! `ironcrab::execution::intent_delivery` (Transport-Wahl, Redelivery-Klassifikation):
+//! - Optionaler Stream `TRADE_INTENTS` mit `Nats-Msg-Id = intent_id` (Publish-Dedup).
+//! - Durable Consumer mit expliziten Acks; Ack erst nachdem das Outcome geschrieben ist.
+//! - Redelivery respektiert TTL (abgelaufen → genau ein `Expired`-Outcome, dann Ack) und
+//!   verarbeitet bereits entschiedene Intents nicht erneut.
+//! - Core-NATS bleibt Default und pro `source` waehlbar.
+//! - Live-Test gegen lokalen `nats-server` (SKIP ohne Binary): Dedup im Fenster, Redelivery bis
+//!   zum Ack nach geschriebenem Outcome.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; kein zweites Outcome pro Intent (I-11).
+
+mod common;
+
+use async_nats::jetstream::consumer::{AckPolicy, PullConsumer};
+use async_nats::jetstream::stream::RetentionPolicy;
+use async_nats::jetstream::AckKind;
+use common::request_reply_e2e_harness::RequestReplyE2eHarness;
+use futures::StreamExt;
+use ironcrab::execution::intent_delivery::{
+    classify_delivery, DeliveryAction, IntentDeliveryTracker, IntentProcessingState,
+    IntentTransport, IntentTransportConfig,
+};
+use ironcrab::ipc::{
+    ExplicitAmount, IntentOrigin, IntentTier, TradeIntent, TradeResources, TradeSide, TradingRegime,
+};
+use ironcrab::nats::jetstream::{
+    intent_publish_headers, trade_intent_subject, trade_intents_consumer_config,
+    trade_intents_stream_config, TRADE_INTENTS_CONSUMER, TRADE_INTENTS_STREAM,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const MINT: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const POOL: &str = "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP";
+const TTL_MS: u64 = 5_000;
+
+fn intent(intent_id: &str) -> TradeIntent {
+    TradeIntent::new(
+        "momentum-bot",
+        "v0.1.0",
+        "run-a62",
+        intent_id.to_string(),
+        "momentum-bot",
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(10_000_000, 9),
+        TradeResources {
+            input_mint: WSOL_MINT.to_string(),
+            output_mint: MINT.to_string(),
+            pools: vec![POOL.to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        TTL_MS,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    )
+}
+
+/// Stream: eigener Subject-Raum (nicht `ironcrab.v1.trade_intents`, sonst faengt der Stream
+/// Core-NATS-Publishes), WorkQueue, Dedup-Fenster >= maximale Intent-TTL.
+#[test]
+fn a62_stream_config_dedups_by_msg_id() {
+    assert_eq!(TRADE_INTENTS_STREAM, "TRADE_INTENTS");
+    assert_eq!(
+        trade_intent_subject("momentum-bot"),
+        "ironcrab.trade_intents.momentum-bot"
+    );
+    let cfg = trade_intents_stream_config();
+    assert_eq!(cfg.name, TRADE_INTENTS_STREAM);
+    assert_eq!(cfg.subjects, vec!["ironcrab.trade_intents.>".to_string()]);
+    assert_eq!(cfg.retention, RetentionPolicy::WorkQueue);
+    assert!(cfg.duplicate_window >= Duration::from_secs(120));
+}
+
+fn msg_id(intent: &TradeIntent) -> Option<String> {
+    intent_publish_headers(intent)
+        .get("Nats-Msg-Id")
+        .map(|v| v.as_str().to_string())
+}
+
+/// Publish-Header: `Nats-Msg-Id` = `intent_id` → gleicher Intent zweimal publiziert wird vom
+/// Server verworfen, ein anderer Intent nicht.
+#[test]
+fn a62_publish_header_uses_intent_id_as_msg_id() {
+    let i = intent("int-dedup-1");
+    assert_eq!(msg_id(&i).as_deref(), Some("int-dedup-1"));
+    assert_eq!(
+        msg_id(&intent("int-dedup-2")).as_deref(),
+        Some("int-dedup-2"),
+        "anderer Intent → andere Msg-Id, kein falsches Dedup"
+    );
+    assert_ne!(msg_id(&i), msg_id(&intent("int-dedup-2")));
+}
+
+/// Consumer: durable, explizite Acks, begrenzte Redeliveries.
+#[test]
+fn a62_consumer_is_durable_with_explicit_acks() {
+    assert_eq!(TRADE_INTENTS_CONSUMER, "execution-engine-intents");
+    let cfg = trade_intents_consumer_config();
+    assert_eq!(cfg.durable_name.as_deref(), Some(TRADE_INTENTS_CONSUMER));
+    assert_eq!(cfg.ack_policy, AckPolicy::Explicit);
+    assert!(cfg.max_deliver > 1);
+    assert!(cfg.ack_wait >= Duration::from_secs(5));
+}
+
+/// Frische Zustellung → verarbeiten; Ack erst nach geschriebenem Outcome.
+#[test]
+fn a62_fresh_delivery_is_processed() {
+    let i = intent("int-1");
+    let now = i.header.ts_unix_ms + 100;
+    assert_eq!(
+        classify_delivery(&i, 1, now, IntentProcessingState::Unknown),
+        DeliveryAction::Process
+    );
+}
+
+/// Redelivery nach Restart: Outcome bereits geschrieben → nur Ack, keine zweite Verarbeitung.
+#[test]
+fn a62_redelivery_after_recorded_outcome_only_acks() {
+    let i = intent("int-1");
+    let now = i.header.ts_unix_ms + 100;
+    assert_eq!(
+        classify_delivery(&i, 2, now, IntentProcessingState::Recorded),
+        DeliveryAction::AckWithoutProcessing
+    );
+}
+
+/// Redelivery waehrend der Verarbeitung (ack_wait zu knapp) → Ack-Wait verlaengern.
+#[test]
+fn a62_redelivery_while_in_flight_extends_ack_wait() {
+    let i = intent("int-1");
+    let now = i.header.ts_unix_ms + 100;
+    assert_eq!(
+        classify_delivery(&i, 2, now, IntentProcessingState::InFlight),
+        DeliveryAction::ExtendAckWait
+    );
+}
+
+/// TTL abgelaufen und noch kein Outcome → genau ein `Expired`-Outcome, dann Ack.
+/// Bereits entschiedene Intents bleiben auch nach TTL bei `AckWithoutProcessing`.
+#[test]
+fn a62_redelivery_respects_ttl() {
+    let i = intent("int-1");
+    let expired = i.header.ts_unix_ms + TTL_MS + 1;
+    assert_eq!(
+        classify_delivery(&i, 3, expired, IntentProcessingState::Unknown),
+        DeliveryAction::ExpireAndAck
+    );
+    assert_eq!(
+        classify_delivery(&i, 1, expired, IntentProcessingState::Unknown),
+        DeliveryAction::ExpireAndAck,
+        "auch die erste Zustellung nach TTL wird nicht mehr ausgefuehrt"
+    );
+    assert_eq!(
+        classify_delivery(&i, 3, expired, IntentProcessingState::Recorded),
+        DeliveryAction::AckWithoutProcessing
+    );
+    assert_eq!(
+        classify_delivery(
+            &i,
+            1,
+            i.header.ts_unix_ms + TTL_MS,
+            IntentProcessingState::Unknown
+        ),
+        DeliveryAction::Process,
+        "Grenze: now == ts + ttl ist noch gueltig (wie ttl_valid)"
+    );
+}
+
+/// Ack erst nach geschriebenem Outcome: solange der Intent in Verarbeitung ist (auch bei
+/// `ExpireAndAck` bis das `Expired`-Outcome steht), gibt der Tracker kein Ack frei.
+#[test]
+fn a62_ack_only_after_outcome_is_recorded() {
+    let mut tracker = IntentDeliveryTracker::default();
+    let i = intent("int-ack-1");
+    let now = i.header.ts_unix_ms + 100;
+    assert_eq!(tracker.state("int-ack-1"), IntentProcessingState::Unknown);
+    assert!(!tracker.may_ack("int-ack-1"));
+
+    assert_eq!(tracker.on_delivery(&i, 1, now), DeliveryAction::Process);
+    assert_eq!(tracker.state("int-ack-1"), IntentProcessingState::InFlight);
+    assert!(!tracker.may_ack("int-ack-1"), "in Verarbeitung: kein Ack");
+    assert_eq!(
+        tracker.on_delivery(&i, 2, now + 50),
+        DeliveryAction::ExtendAckWait
+    );
+
+    tracker.record_outcome("int-ack-1");
+    assert_eq!(tracker.state("int-ack-1"), IntentProcessingState::Recorded);
+    assert!(tracker.may_ack("int-ack-1"));
+    assert_eq!(
+        tracker.on_delivery(&i, 3, now + 100),
+        DeliveryAction::AckWithoutProcessing
+    );
+
+    let late = intent("int-ack-2");
+    let expired = late.header.ts_unix_ms + TTL_MS + 1;
+    assert_eq!(
+        tracker.on_delivery(&late, 1, expired),
+        DeliveryAction::ExpireAndAck
+    );
+    assert!(
+        !tracker.may_ack("int-ack-2"),
+        "Ack erst nach geschriebenem Expired-Outcome"
+    );
+    tracker.record_outcome("int-ack-2");
+    assert!(tracker.may_ack("int-ack-2"));
+    assert_eq!(
+        tracker.on_delivery(&late, 2, expired + 1),
+        DeliveryAction::AckWithoutProcessing,
+        "kein zweites Expired-Outcome"
+    );
+}
+
+async fn next_message(consumer: &PullConsumer) -> async_nats::jetstream::Message {
+    let mut batch = consumer
+        .fetch()
+        .max_messages(1)
+        .expires(Duration::from_secs(5))
+        .messages()
+        .await
+        .unwrap();
+    batch
+        .next()
+        .await
+        .expect("Nachricht innerhalb von 5 s")
+        .unwrap()
+}
+
+/// Live gegen `nats-server`: Republish mit gleicher Msg-Id im Dedup-Fenster wird verworfen; eine
+/// nicht geackte Nachricht kommt nach `ack_wait` erneut, und der Ack folgt erst dem Outcome.
+/// Benoetigt nats-server im PATH — sonst Skip.
+#[test]
+fn a62_jetstream_suppresses_duplicates_and_acks_after_outcome() {
+    let mut harness = RequestReplyE2eHarness::new().expect("harness new");
+    if let Err(e) = harness.start_nats() {
+        if e.contains("nats-server nicht gefunden") {
+            eprintln!("SKIP: {}", e);
+            return;
+        }
+        panic!("nats start: {}", e);
+    }
+    let nats_url = harness.nats_url().to_string();
+
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    rt.block_on(async {
+        let client = async_nats::connect(&nats_url).await.unwrap();
+        let js = async_nats::jetstream::new(client);
+        let mut stream = js
+            .create_stream(trade_intents_stream_config())
+            .await
+            .unwrap();
+        let subject = trade_intent_subject("momentum-bot");
+
+        let first = intent("int-js-1");
+        let second = intent("int-js-2");
+        let mut acks = Vec::new();
+        for i in [&first, &first, &second] {
+            let ack = js
+                .publish_with_headers(
+                    subject.clone(),
+                    intent_publish_headers(i),
+                    serde_json::to_vec(i).unwrap().into(),
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+            acks.push(ack);
+        }
+        assert!(!acks[0].duplicate);
+        assert!(acks[1].duplicate, "gleiche Msg-Id im Dedup-Fenster");
+        assert_eq!(acks[1].sequence, acks[0].sequence);
+        assert!(!acks[2].duplicate, "anderer Intent wird gespeichert");
+        assert_eq!(stream.info().await.unwrap().state.messages, 2);
+
+        // Kurzes ack_wait und max_ack_pending = 1: die naechste Zustellung ist die Redelivery.
+        let mut consumer_cfg = trade_intents_consumer_config();
+        consumer_cfg.ack_wait = Duration::from_secs(1);
+        consumer_cfg.max_ack_pending = 1;
+        let consumer: PullConsumer = stream.create_consumer(consumer_cfg).await.unwrap();
+        let mut tracker = IntentDeliveryTracker::default();
+        let now = first.header.ts_unix_ms + 100;
+
+        let msg = next_message(&consumer).await;
+        let delivered: TradeIntent = serde_json::from_slice(&msg.payload).unwrap();
+        assert_eq!(delivered.intent_id, "int-js-1");
+        assert_eq!(msg.info().unwrap().delivered, 1);
+        assert_eq!(
+            tracker.on_delivery(&delivered, 1, now),
+            DeliveryAction::Process
+        );
+        assert!(!tracker.may_ack("int-js-1"));
+        // Kein Ack ohne Outcome → Redelivery nach ack_wait.
+
+        let redelivered = next_message(&consumer).await;
+        let info = redelivered.info().unwrap();
+        assert_eq!(info.delivered, 2, "nicht geackt → erneut zugestellt");
+        let again: TradeIntent = serde_json::from_slice(&redelivered.payload).unwrap();
+        assert_eq!(again.intent_id, "int-js-1");
+        assert_eq!(
+            tracker.on_delivery(&again, 2, now + 1_000),
+            DeliveryAction::ExtendAckWait
+        );
+        redelivered.ack_with(AckKind::Progress).await.unwrap();
+
+        tracker.record_outcome("int-js-1");
+        assert!(tracker.may_ack("int-js-1"));
+        redelivered.double_ack().await.unwrap();
+
+        let next = next_message(&consumer).await;
+        let next_intent: TradeIntent = serde_json::from_slice(&next.payload).unwrap();
+        assert_eq!(next_intent.intent_id, "int-js-2");
+        assert_eq!(next.info().unwrap().delivered, 1);
+        assert_eq!(
+            tracker.on_delivery(&next_intent, 1, now),
+            DeliveryAction::Process
+        );
+        tracker.record_outcome("int-js-2");
+        next.double_ack().await.unwrap();
+
+        assert_eq!(
+            stream.info().await.unwrap().state.messages,
+            0,
+            "WorkQueue: geackte Intents sind entfernt"
+        );
+    });
+    harness.stop();
+}
+
+/// Transport pro Source waehlbar; Default bleibt Core-NATS (Golden Replays unveraendert).
+#[test]
+fn a62_transport_selectable_per_source() {
+    let default_cfg = IntentTransportConfig::default();
+    assert_eq!(
+        default_cfg.transport_for("momentum-bot"),
+        IntentTransport::CoreNats
+    );
+
+    let cfg = IntentTransportConfig {
+        default: IntentTransport::CoreNats,
+        per_source: HashMap::from([("momentum-bot".to_string(), IntentTransport::JetStream)]),
+    };
+    assert_eq!(
+        cfg.transport_for("momentum-bot"),
+        IntentTransport::JetStream
+    );
+    assert_eq!(cfg.transport_for("arb-strategy"), IntentTransport::CoreNats);
+
+    let json = serde_json::to_value(&cfg).unwrap();
+    assert_eq!(json["per_source"]["momentum-bot"], "jetstream");
+    assert_eq!(json["default"], "core_nats");
+    let parsed: IntentTransportConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, cfg);
+}
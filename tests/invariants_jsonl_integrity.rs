@@ -0,0 +1,342 @@
+//! Invariante A.72: Integritaetskette fuer JSONL-Logs (INVARIANTS.md A.72)
+//!
+//! Blackbox gegen `ironcrab::storage::integrity` + `jsonl_writer` (`with_integrity`):
+//! - Optional pro Writer: Header-Zeile, pro Record `_seq` (lueckenlos) und `_chain`
+//!   (rollierender SHA-256), Footer bei Rotation und `close()`.
+//! - Verifier erkennt abgeschnittene Zeilen, Sequenzluecken und manipulierte Records.
+//! - Drops bei voller Queue (I-4b) verschwinden nicht, sondern stehen als explizite
+//!   Gap-Marker in der Datei.
+//! - Ohne `with_integrity` bleibt das Format unveraendert (Golden Replays, A.10).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Hot Path bleibt nicht blockierend.
+
+use ironcrab::storage::integrity::{
+    strip_integrity, verify_jsonl_file, verify_jsonl_stream, IntegrityIssue, CHAIN_KEY,
+    INTEGRITY_MARKER_KEY, SEQ_KEY,
+};
+use ironcrab::storage::jsonl_writer::{JsonlWriterConfig, QueuedJsonlWriter};
+use serde::ser::SerializeMap;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::tempdir;
+
+fn integrity_writer(dir: &Path, stem: &str) -> QueuedJsonlWriter {
+    let config = JsonlWriterConfig::new(stem)
+        .with_log_dir(dir)
+        .with_flush_each_write(true)
+        .with_integrity(true);
+    QueuedJsonlWriter::spawn(config, 256).unwrap()
+}
+
+fn write_decisions(writer: &QueuedJsonlWriter, n: usize) {
+    for i in 1..=n {
+        assert!(writer.try_enqueue_json(format!(
+            "{{\"decision_id\":\"dec-{i:04}\",\"outcome\":\"Rejected\"}}"
+        )));
+    }
+}
+
+fn files_with_stem(dir: &Path, stem: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&format!("{stem}-")) && n.ends_with(".jsonl"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn single_file(dir: &Path, stem: &str) -> PathBuf {
+    let files = files_with_stem(dir, stem);
+    assert_eq!(files.len(), 1, "{files:?}");
+    files[0].clone()
+}
+
+fn lines(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn write_lines(path: &Path, lines: &[String]) {
+    fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+/// Sauber geschriebene und geschlossene Datei: Header, lueckenlose `_seq`, Footer, intakt.
+#[test]
+fn a72_writer_emits_header_sequence_chain_and_footer() {
+    let dir = tempdir().unwrap();
+    let writer = integrity_writer(dir.path(), "a72_ok");
+    write_decisions(&writer, 5);
+    writer.close().unwrap();
+
+    let path = single_file(dir.path(), "a72_ok");
+    let all = lines(&path);
+    assert_eq!(all.len(), 7, "Header + 5 Records + Footer");
+
+    let header: Value = serde_json::from_str(&all[0]).unwrap();
+    assert_eq!(header[INTEGRITY_MARKER_KEY], "header");
+    let footer: Value = serde_json::from_str(&all[6]).unwrap();
+    assert_eq!(footer[INTEGRITY_MARKER_KEY], "footer");
+    assert_eq!(footer["records"], 5);
+    assert_eq!(footer["last_seq"], 5);
+
+    let mut chains = std::collections::HashSet::new();
+    for (i, line) in all[1..6].iter().enumerate() {
+        let v: Value = serde_json::from_str(line).unwrap();
+        assert_eq!(v[SEQ_KEY], (i + 1) as u64, "`_seq` 1-basiert, lueckenlos");
+        let chain = v[CHAIN_KEY].as_str().expect("`_chain` als String");
+        assert_eq!(chain.len(), 64, "SHA-256 hex");
+        assert!(
+            chains.insert(chain.to_string()),
+            "Kette wiederholt sich nicht"
+        );
+        assert_eq!(v["decision_id"], format!("dec-{:04}", i + 1));
+    }
+    assert_eq!(
+        footer["chain"],
+        serde_json::from_str::<Value>(&all[5]).unwrap()[CHAIN_KEY]
+    );
+
+    let report = verify_jsonl_file(&path).unwrap();
+    assert!(report.is_intact(), "{:?}", report.issues);
+    assert!(report.sealed);
+    assert_eq!(report.records, 5);
+    assert_eq!(report.last_seq, 5);
+    assert_eq!(report.dropped, 0);
+}
+
+/// Prozess stirbt mitten im Schreiben: abgeschnittene letzte Zeile, kein Footer.
+#[test]
+fn a72_truncated_tail_is_detected() {
+    let dir = tempdir().unwrap();
+    let writer = integrity_writer(dir.path(), "a72_trunc");
+    write_decisions(&writer, 4);
+    writer.close().unwrap();
+
+    let path = single_file(dir.path(), "a72_trunc");
+    let body = fs::read_to_string(&path).unwrap();
+    let without_footer = &body[..body.trim_end().rfind('\n').unwrap() + 1];
+    let last_record_start = without_footer.trim_end().rfind('\n').unwrap() + 1;
+    let cut = last_record_start + (without_footer.len() - last_record_start) / 2;
+    fs::write(&path, &without_footer[..cut]).unwrap();
+
+    let report = verify_jsonl_file(&path).unwrap();
+    assert!(!report.is_intact());
+    assert!(!report.sealed, "kein Footer → nicht versiegelt");
+    assert_eq!(
+        report.issues,
+        vec![IntegrityIssue::TruncatedLine { line: 5 }],
+        "Header = Zeile 1, Records 1..3 = Zeilen 2..4, abgeschnittener Record 4 = Zeile 5"
+    );
+    assert_eq!(report.records, 3, "nur vollstaendige Records zaehlen");
+}
+
+/// Geloeschte Zeile: Sequenzluecke wird mit erwarteter und gefundener `_seq` gemeldet.
+#[test]
+fn a72_sequence_gap_is_detected() {
+    let dir = tempdir().unwrap();
+    let writer = integrity_writer(dir.path(), "a72_gap");
+    write_decisions(&writer, 5);
+    writer.close().unwrap();
+
+    let path = single_file(dir.path(), "a72_gap");
+    let mut all = lines(&path);
+    all.remove(3); // Record `_seq` 3
+    write_lines(&path, &all);
+
+    let report = verify_jsonl_file(&path).unwrap();
+    assert!(!report.is_intact());
+    assert!(
+        report.issues.contains(&IntegrityIssue::SequenceGap {
+            line: 4,
+            expected: 3,
+            found: 4,
+        }),
+        "{:?}",
+        report.issues
+    );
+}
+
+/// Manipulierter Record: genau diese Zeile bricht die Kette (lokale Pruefung gegen Vorgaenger).
+#[test]
+fn a72_tampered_record_breaks_chain() {
+    let dir = tempdir().unwrap();
+    let writer = integrity_writer(dir.path(), "a72_tamper");
+    write_decisions(&writer, 5);
+    writer.close().unwrap();
+
+    let path = single_file(dir.path(), "a72_tamper");
+    let mut all = lines(&path);
+    assert!(all[3].contains("dec-0003"));
+    all[3] = all[3].replace("Rejected", "Executed");
+    write_lines(&path, &all);
+
+    let report = verify_jsonl_file(&path).unwrap();
+    assert_eq!(
+        report.issues,
+        vec![IntegrityIssue::ChainMismatch { line: 4, seq: 3 }]
+    );
+}
+
+/// Drops bei voller Queue erscheinen als Gap-Marker mit Anzahl; Datei bleibt intakt.
+#[test]
+fn a72_queue_drops_become_explicit_gap_markers() {
+    const CAP: usize = 4;
+    let dir = tempdir().unwrap();
+    let config = JsonlWriterConfig::new("a72_drop")
+        .with_log_dir(dir.path())
+        .with_flush_each_write(true)
+        .with_integrity(true);
+    let writer = QueuedJsonlWriter::spawn(config, CAP).unwrap();
+
+    let hold = Arc::new(AtomicBool::new(true));
+    assert!(writer.try_write(HoldRecord {
+        hold: Arc::clone(&hold)
+    }));
+    for i in 0..(CAP - 1) {
+        assert!(writer.try_enqueue_json(format!("{{\"fill\":{i}}}")));
+    }
+    let mut dropped = 0u64;
+    for i in 0..7 {
+        if !writer.try_enqueue_json(format!("{{\"overflow\":{i}}}")) {
+            dropped += 1;
+        }
+    }
+    assert!(dropped > 0, "Queue muss voll sein");
+
+    hold.store(false, Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(writer.try_enqueue_json("{\"after\":true}".to_string()));
+    writer.close().unwrap();
+
+    let path = single_file(dir.path(), "a72_drop");
+    let markers: Vec<Value> = lines(&path)
+        .iter()
+        .map(|l| serde_json::from_str::<Value>(l).unwrap())
+        .filter(|v| v[INTEGRITY_MARKER_KEY] == "gap")
+        .collect();
+    let marked: u64 = markers.iter().map(|m| m["dropped"].as_u64().unwrap()).sum();
+    assert_eq!(
+        marked, dropped,
+        "jeder Drop ist in einem Gap-Marker gezaehlt"
+    );
+    for m in &markers {
+        assert!(m[SEQ_KEY].is_u64(), "Gap-Marker belegt eine `_seq`");
+        assert!(m[CHAIN_KEY].is_string(), "Gap-Marker ist Teil der Kette");
+    }
+
+    let report = verify_jsonl_file(&path).unwrap();
+    assert!(report.is_intact(), "{:?}", report.issues);
+    assert_eq!(report.dropped, dropped);
+    assert_eq!(report.gaps, markers.len() as u64);
+    assert_eq!(report.records, 1 + (CAP as u64 - 1) + 1);
+}
+
+/// Rotation: jede rotierte Datei bekommt einen Footer; die naechste verweist im Header auf sie.
+#[test]
+fn a72_rotation_seals_files_and_links_stream() {
+    let dir = tempdir().unwrap();
+    let config = JsonlWriterConfig::new("a72_rot")
+        .with_log_dir(dir.path())
+        .with_flush_each_write(true)
+        .with_max_file_bytes(1_024)
+        .with_integrity(true);
+    let writer = QueuedJsonlWriter::spawn(config, 256).unwrap();
+    write_decisions(&writer, 60);
+    writer.close().unwrap();
+
+    let files = files_with_stem(dir.path(), "a72_rot");
+    assert!(files.len() >= 2, "Groessen-Rotation erwartet: {files:?}");
+    for f in &files {
+        let report = verify_jsonl_file(f).unwrap();
+        assert!(report.sealed, "{f:?}: Footer bei Rotation bzw. close()");
+        assert!(report.is_intact(), "{f:?}: {:?}", report.issues);
+    }
+
+    let stream = verify_jsonl_stream(dir.path(), "a72_rot").unwrap();
+    assert!(stream.is_intact(), "{:?}", stream.issues);
+    assert_eq!(stream.records, 60);
+    assert_eq!(stream.files.len(), files.len());
+
+    // Mittlere Datei entfernt → Verweis des Nachfolgers laeuft ins Leere.
+    if files.len() >= 3 {
+        fs::remove_file(&files[1]).unwrap();
+        let stream = verify_jsonl_stream(dir.path(), "a72_rot").unwrap();
+        assert!(!stream.is_intact());
+        assert!(
+            stream
+                .issues
+                .iter()
+                .any(|i| matches!(i, IntegrityIssue::BrokenLink { .. })),
+            "{:?}",
+            stream.issues
+        );
+    }
+}
+
+/// Ohne `with_integrity`: Format unveraendert; Leser entfernen Marker und Integritaetsfelder.
+#[test]
+fn a72_integrity_is_optional_and_transparent_to_readers() {
+    let dir = tempdir().unwrap();
+    let plain = QueuedJsonlWriter::spawn(
+        JsonlWriterConfig::new("a72_plain")
+            .with_log_dir(dir.path())
+            .with_flush_each_write(true),
+        64,
+    )
+    .unwrap();
+    write_decisions(&plain, 2);
+    plain.close().unwrap();
+    let plain_lines = lines(&single_file(dir.path(), "a72_plain"));
+    assert_eq!(plain_lines.len(), 2, "kein Header/Footer ohne Integritaet");
+    for l in &plain_lines {
+        let v: Value = serde_json::from_str(l).unwrap();
+        assert!(v.get(SEQ_KEY).is_none() && v.get(CHAIN_KEY).is_none());
+    }
+
+    let writer = integrity_writer(dir.path(), "a72_strip");
+    write_decisions(&writer, 2);
+    writer.close().unwrap();
+    let records: Vec<Value> = lines(&single_file(dir.path(), "a72_strip"))
+        .iter()
+        .filter_map(|l| strip_integrity(l).unwrap())
+        .collect();
+    let originals: Vec<Value> = plain_lines
+        .iter()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(
+        records, originals,
+        "strip_integrity liefert exakt die Original-Records, Marker → None"
+    );
+}
+
+/// Haelt den Writer-Thread beim Serialisieren fest, damit die Queue zuverlaessig voll wird.
+struct HoldRecord {
+    hold: Arc<AtomicBool>,
+}
+
+impl serde::Serialize for HoldRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        while self.hold.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("hold", &true)?;
+        map.end()
+    }
+}
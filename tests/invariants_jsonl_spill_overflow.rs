@@ -0,0 +1,274 @@
+//! Invariante A.73: Spill-to-Disk-Overflow fuer QueuedJsonlWriter (INVARIANTS.md A.73)
+//!
+//! Blackbox gegen `ironcrab::storage::jsonl_writer` (`with_overflow_ring`, `overflow_stats`):
+//! - Ist die primaere bounded Queue voll, gehen Records in einen lock-freien Overflow-Ring,
+//!   den ein eigener Thread in eine separate Spill-Datei `{stem}_spill-YYYYMMDD.jsonl` leert.
+//! - Gedroppt (und gezaehlt) wird erst, wenn beide Stufen voll sind; mit Integritaetskette
+//!   (A.72) erscheinen nur diese Drops als Gap-Marker.
+//! - Der Hot Path blockiert weiterhin nie (I-4b); ohne Overflow-Ring bleibt alles wie bisher.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Hot Path bleibt nicht blockierend.
+
+use ironcrab::ipc::{MarketEvent, MarketEventKind};
+use ironcrab::storage::jsonl_writer::{JsonlWriterConfig, QueuedJsonlWriter, SPILL_STEM_SUFFIX};
+use serde::ser::SerializeMap;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+const CAP: usize = 4;
+
+fn spill_config(dir: &Path, stem: &str, ring: usize) -> JsonlWriterConfig {
+    JsonlWriterConfig::new(stem)
+        .with_log_dir(dir)
+        .with_flush_each_write(true)
+        .with_overflow_ring(ring)
+}
+
+/// Belegt die primaere Queue: gehaltener Record + (CAP - 1) Fueller.
+fn saturate_primary(writer: &QueuedJsonlWriter, tag: &str) -> Arc<AtomicBool> {
+    let hold = Arc::new(AtomicBool::new(true));
+    assert!(writer.try_write(HoldRecord {
+        id: format!("{tag}-hold"),
+        hold: Arc::clone(&hold),
+    }));
+    for i in 0..(CAP - 1) {
+        assert!(writer.try_enqueue_json(format!("{{\"id\":\"{tag}-fill-{i}\"}}")));
+    }
+    hold
+}
+
+fn files_with_stem(dir: &Path, stem: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&format!("{stem}-")) && n.ends_with(".jsonl"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn ids_in(files: &[PathBuf]) -> Vec<String> {
+    let mut out = Vec::new();
+    for f in files {
+        for line in fs::read_to_string(f).unwrap().lines() {
+            let v: Value = serde_json::from_str(line).unwrap();
+            if let Some(id) = v["id"].as_str().or(v["event_id"].as_str()) {
+                out.push(id.to_string());
+            }
+        }
+    }
+    out
+}
+
+fn wait_until(mut cond: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !cond() {
+        assert!(Instant::now() < deadline, "Timeout beim Warten auf Writer");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Ohne Overflow-Ring: unveraendertes I-4b-Verhalten (volle Queue → `false`), keine Spill-Datei.
+#[test]
+fn a73_overflow_tier_is_opt_in() {
+    let dir = tempdir().unwrap();
+    let config = JsonlWriterConfig::new("a73_off")
+        .with_log_dir(dir.path())
+        .with_flush_each_write(true);
+    let writer = QueuedJsonlWriter::spawn(config, CAP).unwrap();
+    let hold = saturate_primary(&writer, "off");
+
+    assert!(!writer.try_enqueue_json("{\"id\":\"off-overflow\"}".to_string()));
+    let stats = writer.overflow_stats();
+    assert_eq!(stats.spilled, 0);
+    assert_eq!(stats.dropped, 1, "Drop wird auch ohne Ring gezaehlt");
+    assert!(writer.spill_file().is_none());
+
+    hold.store(false, Ordering::Relaxed);
+    writer.close().unwrap();
+    assert!(
+        files_with_stem(dir.path(), &format!("a73_off{SPILL_STEM_SUFFIX}")).is_empty(),
+        "keine Spill-Datei ohne Ring"
+    );
+}
+
+/// Burst bei voller primaerer Queue: alles landet im Ring und danach in der Spill-Datei, nichts geht verloren.
+#[test]
+fn a73_burst_spills_to_separate_file_without_loss() {
+    assert_eq!(SPILL_STEM_SUFFIX, "_spill");
+    let dir = tempdir().unwrap();
+    let writer = QueuedJsonlWriter::spawn(spill_config(dir.path(), "a73_burst", 256), CAP).unwrap();
+    let hold = saturate_primary(&writer, "burst");
+
+    let mut sent = vec!["burst-hold".to_string()];
+    sent.extend((0..CAP - 1).map(|i| format!("burst-fill-{i}")));
+    for i in 0..50 {
+        let id = format!("burst-spill-{i:02}");
+        assert!(
+            writer.try_enqueue_json(format!("{{\"id\":\"{id}\"}}")),
+            "{id}: Ring hat Platz → angenommen"
+        );
+        sent.push(id);
+    }
+    let event = MarketEvent::new(
+        "market-data",
+        "eval-a73",
+        "run-a73",
+        "burst-event".to_string(),
+        "geyser",
+        Some(1),
+        MarketEventKind::SlotUpdate { current_slot: 1 },
+    );
+    assert!(
+        writer.try_enqueue_market_event(&event),
+        "alle try_*-Pfade nutzen den Ring"
+    );
+    sent.push("burst-event".to_string());
+
+    let spill = writer.spill_file().expect("Spill-Datei aktiv");
+    let name = spill.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(
+        name.starts_with("a73_burst_spill-") && name.ends_with(".jsonl"),
+        "{name}"
+    );
+    assert_eq!(spill.parent().unwrap(), dir.path());
+
+    hold.store(false, Ordering::Relaxed);
+    writer.flush().unwrap();
+    wait_until(|| writer.overflow_stats().spill_written == 51);
+    let stats = writer.overflow_stats();
+    assert_eq!(stats.spilled, 51);
+    assert_eq!(stats.dropped, 0);
+    writer.close().unwrap();
+
+    let primary = files_with_stem(dir.path(), "a73_burst");
+    let spilled = files_with_stem(dir.path(), "a73_burst_spill");
+    let spill_ids = ids_in(&spilled);
+    assert_eq!(spill_ids.len(), 51);
+    assert!(spill_ids
+        .iter()
+        .all(|id| id.starts_with("burst-spill-") || id == "burst-event"));
+
+    let mut all = ids_in(&primary);
+    all.extend(spill_ids);
+    assert_eq!(all.len(), sent.len(), "jeder Record genau einmal");
+    assert_eq!(
+        all.into_iter().collect::<BTreeSet<_>>(),
+        sent.into_iter().collect::<BTreeSet<_>>()
+    );
+}
+
+/// Gedroppt wird erst, wenn auch der Ring voll ist; Drop-Zaehler = Zahl der `false`-Rueckgaben.
+#[test]
+fn a73_drops_only_when_both_tiers_full() {
+    const RING: usize = 8;
+    let dir = tempdir().unwrap();
+    let config = spill_config(dir.path(), "a73_full", RING).with_integrity(true);
+    let writer = QueuedJsonlWriter::spawn(config, CAP).unwrap();
+    let hold_primary = saturate_primary(&writer, "full");
+
+    // Erster Spill-Record haelt den Spill-Thread fest → Ring laeuft voll.
+    let hold_spill = Arc::new(AtomicBool::new(true));
+    assert!(writer.try_write(HoldRecord {
+        id: "full-spill-hold".to_string(),
+        hold: Arc::clone(&hold_spill),
+    }));
+
+    let mut accepted = 0u64;
+    let mut dropped = 0u64;
+    let mut first_drop_at = None;
+    for i in 0..(RING * 4) {
+        if writer.try_enqueue_json(format!("{{\"id\":\"full-{i:03}\"}}")) {
+            accepted += 1;
+        } else {
+            dropped += 1;
+            first_drop_at.get_or_insert(i);
+        }
+    }
+    let first_drop_at = first_drop_at.expect("beide Stufen voll → Drops");
+    assert!(
+        first_drop_at >= RING - 1,
+        "Drop erst nach vollem Ring (erster Drop bei {first_drop_at})"
+    );
+    let stats = writer.overflow_stats();
+    assert_eq!(stats.dropped, dropped);
+    assert_eq!(stats.spilled, accepted + 1);
+
+    hold_primary.store(false, Ordering::Relaxed);
+    hold_spill.store(false, Ordering::Relaxed);
+    writer.flush().unwrap();
+    wait_until(|| writer.overflow_stats().spill_written == accepted + 1);
+    writer.close().unwrap();
+
+    // Integritaetskette (A.72): nur echte Drops werden als Gap-Marker gezaehlt.
+    let mut files = files_with_stem(dir.path(), "a73_full");
+    files.extend(files_with_stem(dir.path(), "a73_full_spill"));
+    let mut marked = 0u64;
+    for f in files {
+        for line in fs::read_to_string(&f).unwrap().lines() {
+            let v: Value = serde_json::from_str(line).unwrap();
+            if v["_integrity"] == "gap" {
+                marked += v["dropped"].as_u64().unwrap();
+            }
+        }
+    }
+    assert_eq!(marked, dropped, "gespillte Records sind keine Luecke");
+}
+
+/// Hot Path: auch bei vollen Stufen kehrt jedes `try_*` sofort zurueck.
+#[test]
+fn a73_hot_path_never_blocks() {
+    let dir = tempdir().unwrap();
+    let writer = QueuedJsonlWriter::spawn(spill_config(dir.path(), "a73_hot", 16), CAP).unwrap();
+    let hold_primary = saturate_primary(&writer, "hot");
+    let hold_spill = Arc::new(AtomicBool::new(true));
+    assert!(writer.try_write(HoldRecord {
+        id: "hot-spill-hold".to_string(),
+        hold: Arc::clone(&hold_spill),
+    }));
+
+    let t0 = Instant::now();
+    for i in 0..2_000 {
+        let _ = writer.try_enqueue_json(format!("{{\"id\":\"hot-{i}\"}}"));
+    }
+    assert!(
+        t0.elapsed() < Duration::from_millis(500),
+        "2000 try_enqueue_json bei vollen Stufen dauerten {:?}",
+        t0.elapsed()
+    );
+    assert!(writer.overflow_stats().dropped > 0);
+
+    hold_primary.store(false, Ordering::Relaxed);
+    hold_spill.store(false, Ordering::Relaxed);
+    writer.close().unwrap();
+}
+
+/// Haelt den jeweiligen Writer-Thread beim Serialisieren fest.
+struct HoldRecord {
+    id: String,
+    hold: Arc<AtomicBool>,
+}
+
+impl serde::Serialize for HoldRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        while self.hold.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("id", &self.id)?;
+        map.end()
+    }
+}
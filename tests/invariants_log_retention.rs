@@ -0,0 +1,298 @@
+//! Invariante A.68: Asynchrone Retention, Kompression und Groessen-Budget fuer trade_logs (INVARIANTS.md A.68)
+//!
+//! Blackbox gegen `ironcrab::storage::retention` (+ `jsonl_writer` fuer aktive Datei):
+//! - Geschlossene Rotationsdateien werden zstd-komprimiert (`*.jsonl` → `*.jsonl.zst`).
+//! - Pro Stream-Verzeichnis (`market_events/`, `intents/`, `decisions/`, `executions/`)
+//!   gelten Alters- und Gesamtgroessen-Budget (STORAGE_CONVENTIONS §5: 7–30 Tage).
+//! - Die aktive Datei wird nie angefasst; jede Loeschung wird protokolliert.
+//! - Der Service laeuft im Hintergrund und blockiert keinen Writer (I-4b).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; keine Loeschung ausserhalb des Log-Roots.
+
+use ironcrab::storage::jsonl_writer::{JsonlWriterConfig, QueuedJsonlWriter};
+use ironcrab::storage::retention::{
+    apply_plan, plan_stream_dir, DeletionReason, RetentionConfig, RetentionPolicy,
+    RetentionService, RETENTION_AUDIT_DIR, RETENTION_STREAM_DIRS,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+/// 2025-01-31T12:00:00Z
+const NOW_MS: u64 = 1_738_324_800_000;
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+fn write_file(dir: &Path, name: &str, bytes: usize) -> PathBuf {
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join(name);
+    let line = "{\"decision_id\":\"dec-retention\",\"outcome\":\"Rejected\"}\n";
+    let body: String = line.repeat(bytes / line.len() + 1);
+    fs::write(&path, &body.as_bytes()[..bytes]).unwrap();
+    path
+}
+
+fn policy(max_age_days: u32, max_total_bytes: u64) -> RetentionPolicy {
+    RetentionPolicy {
+        max_age_days,
+        max_total_bytes,
+        compress_closed: true,
+    }
+}
+
+fn no_active() -> HashSet<PathBuf> {
+    HashSet::new()
+}
+
+/// Defaults im Rahmen von STORAGE_CONVENTIONS §5; alle vier Stream-Verzeichnisse abgedeckt.
+#[test]
+fn a68_defaults_cover_stream_dirs_within_convention() {
+    assert_eq!(
+        RETENTION_STREAM_DIRS,
+        ["market_events", "intents", "decisions", "executions"]
+    );
+    let cfg = RetentionConfig::default();
+    for stream in RETENTION_STREAM_DIRS {
+        let p = cfg.policy_for(stream);
+        assert!(
+            (7..=30).contains(&p.max_age_days),
+            "{stream}: {} Tage",
+            p.max_age_days
+        );
+        assert!(p.max_total_bytes > 0);
+        assert!(p.compress_closed);
+    }
+
+    let cfg: RetentionConfig = serde_json::from_str(
+        r#"{"per_stream": {"market_events": {"max_age_days": 7, "max_total_bytes": 1000, "compress_closed": true}}}"#,
+    )
+    .unwrap();
+    assert_eq!(cfg.policy_for("market_events").max_age_days, 7);
+    assert_eq!(
+        cfg.policy_for("decisions"),
+        RetentionConfig::default().policy_for("decisions"),
+        "nicht ueberschriebene Streams behalten den Default"
+    );
+}
+
+/// Geschlossene Dateien werden komprimiert; Original verschwindet erst nach erfolgreichem Schreiben.
+#[test]
+fn a68_closed_files_are_zstd_compressed() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("decisions");
+    let closed = write_file(&dir, "decision_records-20250129.jsonl", 4_096);
+    let today = write_file(&dir, "decision_records-20250131.jsonl", 4_096);
+    let active: HashSet<PathBuf> = [today.clone()].into();
+
+    let plan = plan_stream_dir(&dir, &policy(30, u64::MAX), NOW_MS, &active).unwrap();
+    assert_eq!(plan.compress, vec![closed.clone()]);
+    assert!(plan.delete.is_empty());
+
+    let report = apply_plan(root.path(), &plan).unwrap();
+    assert_eq!(report.compressed, 1);
+    assert!(!closed.exists(), "Original nach Kompression entfernt");
+    let zst = dir.join("decision_records-20250129.jsonl.zst");
+    let bytes = fs::read(&zst).expect("komprimierte Datei");
+    assert_eq!(bytes[..4], ZSTD_MAGIC);
+    assert!(bytes.len() < 4_096);
+    assert!(today.exists());
+    assert_eq!(
+        fs::read(&today).unwrap().len(),
+        4_096,
+        "aktive Datei unveraendert"
+    );
+
+    let again = plan_stream_dir(&dir, &policy(30, u64::MAX), NOW_MS, &active).unwrap();
+    assert!(
+        again.compress.is_empty(),
+        "bereits komprimiert → idempotent"
+    );
+}
+
+/// Alters-Budget: Dateien aelter als `max_age_days` (Datum aus dem Dateinamen, UTC) werden
+/// geloescht, auch bereits komprimierte.
+#[test]
+fn a68_age_budget_deletes_old_files() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("intents");
+    let old = write_file(&dir, "trade_intents-20250101.jsonl", 100);
+    let old_zst = write_file(&dir, "trade_intents-20250102.jsonl.zst", 50);
+    let young = write_file(&dir, "trade_intents-20250130.jsonl", 100);
+
+    let plan = plan_stream_dir(&dir, &policy(7, u64::MAX), NOW_MS, &no_active()).unwrap();
+    let deleted: HashSet<&PathBuf> = plan.delete.iter().map(|d| &d.path).collect();
+    assert!(deleted.contains(&old));
+    assert!(deleted.contains(&old_zst));
+    assert!(!deleted.contains(&young));
+    assert!(plan
+        .delete
+        .iter()
+        .all(|d| d.reason == DeletionReason::MaxAge));
+}
+
+/// Groessen-Budget: aelteste geschlossene Dateien zuerst, bis das Verzeichnis ins Budget passt.
+#[test]
+fn a68_size_budget_deletes_oldest_first() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("market_events");
+    let d27 = write_file(&dir, "market_events-20250127.jsonl.zst", 1_000);
+    let d28 = write_file(&dir, "market_events-20250128.jsonl.zst", 1_000);
+    let d29 = write_file(&dir, "market_events-20250129.jsonl.zst", 1_000);
+    let today = write_file(&dir, "market_events-20250131.jsonl", 1_000);
+    let active: HashSet<PathBuf> = [today.clone()].into();
+
+    let mut p = policy(30, 2_500);
+    p.compress_closed = false;
+    let plan = plan_stream_dir(&dir, &p, NOW_MS, &active).unwrap();
+    let deleted: Vec<&PathBuf> = plan.delete.iter().map(|d| &d.path).collect();
+    assert_eq!(deleted, vec![&d27, &d28], "aelteste zuerst, bis <= 2500 B");
+    assert!(plan
+        .delete
+        .iter()
+        .all(|d| d.reason == DeletionReason::SizeBudget));
+    assert!(!deleted.contains(&&d29));
+}
+
+/// Die aktive Datei wird nie komprimiert oder geloescht — auch wenn sie allein das Budget
+/// sprengt oder ihr Datum alt ist (z. B. Writer ueber Mitternacht).
+#[test]
+fn a68_active_file_is_never_touched() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("executions");
+    let active_old = write_file(&dir, "execution_results-20250101.jsonl", 10_000);
+    let active: HashSet<PathBuf> = [active_old.clone()].into();
+
+    let plan = plan_stream_dir(&dir, &policy(7, 1), NOW_MS, &active).unwrap();
+    assert!(plan.compress.is_empty());
+    assert!(plan.delete.is_empty());
+    apply_plan(root.path(), &plan).unwrap();
+    assert!(active_old.exists());
+}
+
+/// Jede Loeschung landet im Audit-Log (JSONL unter dem Log-Root) mit Pfad, Grund und Bytes.
+#[test]
+fn a68_every_deletion_is_logged() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("decisions");
+    write_file(&dir, "decision_records-20250101.jsonl.zst", 123);
+    write_file(&dir, "decision_records-20250102.jsonl.zst", 456);
+
+    let plan = plan_stream_dir(&dir, &policy(7, u64::MAX), NOW_MS, &no_active()).unwrap();
+    let report = apply_plan(root.path(), &plan).unwrap();
+    assert_eq!(report.deleted.len(), 2);
+    assert_eq!(report.deleted.iter().map(|d| d.bytes).sum::<u64>(), 579);
+
+    let audit_dir = root.path().join(RETENTION_AUDIT_DIR);
+    let lines: Vec<serde_json::Value> = fs::read_dir(&audit_dir)
+        .expect("Audit-Verzeichnis")
+        .flat_map(|e| {
+            let content = fs::read_to_string(e.unwrap().path()).unwrap();
+            content
+                .lines()
+                .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(lines.len(), 2, "eine Audit-Zeile pro Loeschung");
+    for line in &lines {
+        assert!(line["path"]
+            .as_str()
+            .unwrap()
+            .contains("decision_records-2025010"));
+        assert_eq!(line["reason"], "max_age");
+        assert!(line["bytes"].as_u64().unwrap() > 0);
+        assert!(line["ts_unix_ms"].as_u64().is_some());
+    }
+}
+
+/// Groessen-Rotation im JSONL-Writer: Datei ueber `max_file_bytes` → naechste Sequenz-Datei;
+/// `active_file()` zeigt immer auf die aktuell beschriebene Datei.
+#[test]
+fn a68_jsonl_writer_rotates_by_size_and_exposes_active_file() {
+    let dir = tempdir().unwrap();
+    let config = JsonlWriterConfig::new("decision_records")
+        .with_log_dir(dir.path())
+        .with_flush_each_write(true)
+        .with_max_file_bytes(512);
+    let writer = QueuedJsonlWriter::spawn(config, 256).unwrap();
+    for i in 0..64 {
+        assert!(writer.try_enqueue_json(format!("{{\"decision_id\":\"dec-{i:04}\"}}")));
+    }
+    writer.flush().unwrap();
+
+    let files: Vec<PathBuf> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|x| x == "jsonl"))
+        .collect();
+    assert!(
+        files.len() > 1,
+        "Groessen-Limit erzwingt Rotation: {files:?}"
+    );
+    for f in &files {
+        assert!(
+            fs::metadata(f).unwrap().len() <= 512 + 64,
+            "{f:?} ueberschreitet das Limit um mehr als eine Zeile"
+        );
+    }
+    let active = writer.active_file().expect("aktive Datei");
+    assert!(files.contains(&active));
+}
+
+/// Service: Start, Trigger und Shutdown blockieren nicht; waehrenddessen schreibt ein Writer
+/// weiter, und seine aktive Datei ueberlebt ein Budget von 1 Byte.
+#[test]
+fn a68_service_runs_in_background_without_blocking_writers() {
+    let root = tempdir().unwrap();
+    let dir = root.path().join("decisions");
+    fs::create_dir_all(&dir).unwrap();
+    write_file(&dir, "decision_records-20250101.jsonl", 2_048);
+
+    let config = JsonlWriterConfig::new("decision_records")
+        .with_log_dir(&dir)
+        .with_flush_each_write(true);
+    let writer = QueuedJsonlWriter::spawn(config, 1_024).unwrap();
+    assert!(writer.try_enqueue_json("{\"decision_id\":\"warmup\"}".to_string()));
+    writer.flush().unwrap();
+
+    let mut cfg = RetentionConfig::default();
+    cfg.per_stream.insert("decisions".to_string(), policy(7, 1));
+    let started = Instant::now();
+    let service = RetentionService::spawn(
+        root.path().to_path_buf(),
+        cfg,
+        Duration::from_millis(10),
+        writer.active_files(),
+    )
+    .unwrap();
+    assert!(
+        started.elapsed() < Duration::from_millis(50),
+        "spawn kehrt sofort zurueck"
+    );
+
+    for i in 0..500 {
+        let t = Instant::now();
+        assert!(writer.try_enqueue_json(format!("{{\"decision_id\":\"dec-{i}\"}}")));
+        assert!(t.elapsed() < Duration::from_millis(20));
+        if i % 100 == 0 {
+            // Trigger ist best effort (laufender Pass → `false`), aber nie blockierend.
+            let t = Instant::now();
+            let _ = service.try_trigger();
+            assert!(t.elapsed() < Duration::from_millis(20));
+        }
+    }
+    writer.flush().unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    let report = service.shutdown().unwrap();
+
+    assert!(report
+        .deleted
+        .iter()
+        .any(|d| d.path.ends_with("decision_records-20250101.jsonl")
+            || d.path.ends_with("decision_records-20250101.jsonl.zst")));
+    let active = writer.active_file().expect("aktive Datei");
+    assert!(active.exists(), "aktive Datei darf nie geloescht werden");
+    let (records, _) = writer.stats();
+    assert!(records >= 501);
+}
@@ -0,0 +1,258 @@
+//! Invariante A.57: FIFO Lot Ledger mit Realized PnL pro Lot und Intent (INVARIANTS.md A.57)
+//!
+//! Blackbox gegen `ironcrab::storage::lot_ledger`:
+//! - Jeder BUY-Fill wird ein Lot (Menge, Cost Basis inkl. Fees, Entry-Slot, Pool) pro Mint.
+//! - SELL-Fills verbrauchen Lots FIFO; Realized PnL pro Lot und pro SELL-Intent.
+//! - Pro-rata-Rundung ohne Drift: ein vollstaendig verbrauchtes Lot traegt exakt seine Cost Basis.
+//! - Persistenz event-sourced ueber JetStream (`LOT_LEDGER`); Replay ergibt denselben Zustand,
+//!   Redelivery (gleiche `execution_id`) ist idempotent.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; keine Float-Arithmetik in PnL.
+
+use ironcrab::nats::jetstream::{lot_ledger_subject, LOT_LEDGER_STREAM};
+use ironcrab::storage::lot_ledger::{BuyFill, LedgerEvent, LotLedger, SellFill};
+
+const MINT_A: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const MINT_B: &str = "MintBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB";
+const POOL: &str = "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP";
+
+fn buy(
+    exec: &str,
+    intent: &str,
+    mint: &str,
+    amount_raw: u64,
+    cost: u64,
+    fees: u64,
+    slot: u64,
+) -> BuyFill {
+    BuyFill {
+        execution_id: exec.to_string(),
+        intent_id: intent.to_string(),
+        mint: mint.to_string(),
+        pool: POOL.to_string(),
+        amount_raw,
+        cost_lamports: cost,
+        fees_lamports: fees,
+        entry_slot: slot,
+    }
+}
+
+fn sell(
+    exec: &str,
+    intent: &str,
+    mint: &str,
+    amount_raw: u64,
+    proceeds: u64,
+    fees: u64,
+    slot: u64,
+) -> SellFill {
+    SellFill {
+        execution_id: exec.to_string(),
+        intent_id: intent.to_string(),
+        mint: mint.to_string(),
+        amount_raw,
+        proceeds_lamports: proceeds,
+        fees_lamports: fees,
+        slot,
+    }
+}
+
+/// Ein BUY, voller SELL: PnL = (Proceeds - Sell-Fees) - (Cost + Buy-Fees).
+#[test]
+fn a57_single_lot_round_trip_pnl() {
+    let mut ledger = LotLedger::new();
+    assert!(ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000_000, 10_000, 100)));
+
+    let lots = ledger.open_lots(MINT_A);
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots[0].lot_id, "buy-1");
+    assert_eq!(lots[0].remaining_raw, 1_000);
+    assert_eq!(lots[0].remaining_cost_lamports, 100_010_000);
+    assert_eq!(lots[0].entry_slot, 100);
+    assert_eq!(lots[0].pool, POOL);
+
+    let r = ledger
+        .record_sell(sell("e2", "sell-1", MINT_A, 1_000, 120_000_000, 5_000, 200))
+        .expect("neuer Fill");
+    assert_eq!(r.consumed.len(), 1);
+    assert_eq!(r.consumed[0].lot_id, "buy-1");
+    assert_eq!(r.consumed[0].amount_raw, 1_000);
+    assert_eq!(r.consumed[0].cost_basis_lamports, 100_010_000);
+    assert_eq!(r.consumed[0].proceeds_lamports, 119_995_000);
+    assert_eq!(r.realized_pnl_lamports, 119_995_000 - 100_010_000);
+    assert_eq!(r.unmatched_raw, 0);
+    assert!(ledger.open_lots(MINT_A).is_empty());
+    assert_eq!(ledger.open_amount_raw(MINT_A), 0);
+}
+
+/// Scale-in: zwei Lots; SELL verbraucht FIFO erst das aeltere, dann anteilig das juengere.
+#[test]
+fn a57_scale_in_consumed_fifo() {
+    let mut ledger = LotLedger::new();
+    ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 0, 100));
+    ledger.record_buy(buy("e2", "buy-2", MINT_A, 1_000, 200_000, 0, 110));
+
+    let r = ledger
+        .record_sell(sell("e3", "sell-1", MINT_A, 1_500, 450_000, 0, 120))
+        .unwrap();
+    let ids: Vec<_> = r
+        .consumed
+        .iter()
+        .map(|c| (c.lot_id.as_str(), c.amount_raw))
+        .collect();
+    assert_eq!(ids, vec![("buy-1", 1_000), ("buy-2", 500)]);
+    assert_eq!(r.consumed[0].cost_basis_lamports, 100_000);
+    assert_eq!(r.consumed[1].cost_basis_lamports, 100_000);
+    assert_eq!(r.consumed[0].proceeds_lamports, 300_000);
+    assert_eq!(r.consumed[1].proceeds_lamports, 150_000);
+    assert_eq!(r.realized_pnl_lamports, 450_000 - 200_000);
+
+    let open = ledger.open_lots(MINT_A);
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].lot_id, "buy-2");
+    assert_eq!(open[0].remaining_raw, 500);
+    assert_eq!(open[0].remaining_cost_lamports, 100_000);
+}
+
+/// Drei Teil-Exits eines Lots mit unteilbarer Cost Basis: Summe der Cost-Anteile == Cost Basis,
+/// Summe der Proceeds-Anteile == Netto-Proceeds (keine Rundungsdrift).
+#[test]
+fn a57_partial_exits_have_no_rounding_drift() {
+    let mut ledger = LotLedger::new();
+    ledger.record_buy(buy("e1", "buy-1", MINT_A, 3, 100, 0, 1));
+
+    let mut cost_sum = 0;
+    for (i, exec) in ["e2", "e3", "e4"].iter().enumerate() {
+        let r = ledger
+            .record_sell(sell(
+                exec,
+                &format!("sell-{i}"),
+                MINT_A,
+                1,
+                50,
+                1,
+                2 + i as u64,
+            ))
+            .unwrap();
+        assert_eq!(r.consumed.len(), 1);
+        assert_eq!(r.consumed[0].proceeds_lamports, 49);
+        cost_sum += r.consumed[0].cost_basis_lamports;
+    }
+    assert_eq!(cost_sum, 100);
+    assert_eq!(ledger.realized_pnl_for_lot("buy-1"), 3 * 49 - 100);
+}
+
+/// Realized PnL aggregiert pro SELL-Intent und pro Lot (BUY-Intent) ueber mehrere SELLs.
+#[test]
+fn a57_realized_pnl_per_intent_and_per_lot() {
+    let mut ledger = LotLedger::new();
+    ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 0, 100));
+    ledger.record_buy(buy("e2", "buy-2", MINT_A, 1_000, 300_000, 0, 110));
+    ledger.record_sell(sell("e3", "sell-1", MINT_A, 500, 100_000, 0, 120));
+    ledger.record_sell(sell("e4", "sell-2", MINT_A, 1_500, 600_000, 0, 130));
+
+    // sell-1: 500 aus buy-1 (Cost 50_000) → +50_000
+    assert_eq!(ledger.realized_pnl_for_intent("sell-1"), 50_000);
+    // sell-2: 500 aus buy-1 (Cost 50_000, Proceeds 200_000) + 1000 aus buy-2 (Cost 300_000, Proceeds 400_000)
+    assert_eq!(ledger.realized_pnl_for_intent("sell-2"), 150_000 + 100_000);
+    assert_eq!(ledger.realized_pnl_for_lot("buy-1"), 50_000 + 150_000);
+    assert_eq!(ledger.realized_pnl_for_lot("buy-2"), 100_000);
+    assert_eq!(ledger.realized_pnl_total(MINT_A), 300_000);
+}
+
+/// SELL ueber den offenen Bestand: Rest als `unmatched_raw` ohne erfundene Cost Basis.
+#[test]
+fn a57_oversell_reports_unmatched_amount() {
+    let mut ledger = LotLedger::new();
+    ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 0, 100));
+    let r = ledger
+        .record_sell(sell("e2", "sell-1", MINT_A, 1_250, 250_000, 0, 110))
+        .unwrap();
+    assert_eq!(r.consumed.len(), 1);
+    assert_eq!(r.consumed[0].amount_raw, 1_000);
+    assert_eq!(r.consumed[0].proceeds_lamports, 200_000);
+    assert_eq!(r.unmatched_raw, 250);
+    assert_eq!(r.unmatched_proceeds_lamports, 50_000);
+    assert_eq!(
+        r.realized_pnl_lamports, 100_000,
+        "unmatched zaehlt nicht als PnL"
+    );
+}
+
+/// Lots sind pro Mint getrennt.
+#[test]
+fn a57_lots_are_isolated_per_mint() {
+    let mut ledger = LotLedger::new();
+    ledger.record_buy(buy("e1", "buy-a", MINT_A, 1_000, 100_000, 0, 100));
+    ledger.record_buy(buy("e2", "buy-b", MINT_B, 2_000, 50_000, 0, 101));
+    let r = ledger
+        .record_sell(sell("e3", "sell-b", MINT_B, 2_000, 60_000, 0, 102))
+        .unwrap();
+    assert_eq!(r.consumed[0].lot_id, "buy-b");
+    assert_eq!(ledger.open_amount_raw(MINT_A), 1_000);
+    assert_eq!(ledger.open_amount_raw(MINT_B), 0);
+}
+
+/// JetStream-Redelivery: gleiche `execution_id` wird ignoriert.
+#[test]
+fn a57_duplicate_execution_is_idempotent() {
+    let mut ledger = LotLedger::new();
+    assert!(ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 0, 100)));
+    assert!(!ledger.record_buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 0, 100)));
+    assert_eq!(ledger.open_amount_raw(MINT_A), 1_000);
+
+    assert!(ledger
+        .record_sell(sell("e2", "sell-1", MINT_A, 400, 80_000, 0, 110))
+        .is_some());
+    assert!(ledger
+        .record_sell(sell("e2", "sell-1", MINT_A, 400, 80_000, 0, 110))
+        .is_none());
+    assert_eq!(ledger.open_amount_raw(MINT_A), 600);
+    assert_eq!(ledger.realized_pnl_for_intent("sell-1"), 40_000);
+}
+
+/// Persistenz: serialisierte Events (JSON) ergeben per Replay denselben Zustand;
+/// Stream-/Subject-Namen sind stabil.
+#[test]
+fn a57_replay_from_persisted_events_is_deterministic() {
+    assert_eq!(LOT_LEDGER_STREAM, "LOT_LEDGER");
+    assert_eq!(
+        lot_ledger_subject(MINT_A),
+        format!("ironcrab.lot_ledger.{MINT_A}")
+    );
+
+    let events = vec![
+        LedgerEvent::Buy(buy("e1", "buy-1", MINT_A, 1_000, 100_000, 500, 100)),
+        LedgerEvent::Buy(buy("e2", "buy-2", MINT_A, 1_000, 200_000, 500, 110)),
+        LedgerEvent::Sell(sell("e3", "sell-1", MINT_A, 1_500, 450_000, 700, 120)),
+        // Redelivery mitten im Stream
+        LedgerEvent::Sell(sell("e3", "sell-1", MINT_A, 1_500, 450_000, 700, 120)),
+    ];
+
+    let mut live = LotLedger::new();
+    for e in &events {
+        live.apply(e);
+    }
+
+    let wire: Vec<String> = events
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect();
+    let parsed: Vec<LedgerEvent> = wire
+        .iter()
+        .map(|s| serde_json::from_str(s).unwrap())
+        .collect();
+    assert_eq!(parsed, events);
+
+    let replayed = LotLedger::replay(&parsed);
+    assert_eq!(replayed.open_lots(MINT_A), live.open_lots(MINT_A));
+    assert_eq!(
+        replayed.realized_pnl_for_intent("sell-1"),
+        live.realized_pnl_for_intent("sell-1")
+    );
+    assert_eq!(
+        replayed.realized_pnl_total(MINT_A),
+        live.realized_pnl_total(MINT_A)
+    );
+    assert_eq!(replayed.open_amount_raw(MINT_A), 500);
+}
@@ -0,0 +1,461 @@
+//! Invariante A.67: Queued Parquet-Sink fuer MarketEvents und ExecutionResults (INVARIANTS.md A.67)
+//!
+//! Blackbox gegen `ironcrab::storage::parquet_writer`:
+//! - Gleicher Non-Blocking-Vertrag wie `QueuedJsonlWriter` (I-4b): `try_write` wartet nie,
+//!   volle Queue → sofort `false` + Drop-Zaehler.
+//! - Row-Groups werden gepuffert; `MarketEventKind`-Varianten werden in typisierte Spalten
+//!   geflacht (eine `kind`-Spalte + nullable Feld-Spalten).
+//! - Tagesrotation `{stream}-YYYYMMDD.parquet` (UTC, nach `header.ts_unix_ms`).
+//! - Pro Stream einschaltbar; Default aus (JSONL bleibt SSOT, STORAGE_CONVENTIONS §3).
+//! - Geschriebene Dateien werden mit dem `parquet`-Reader zurueckgelesen: Zeilenzahl,
+//!   typisierte Spalten und exakte `Decimal`-Werte (`DECIMAL(38, 18)`).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Hot Path bleibt nicht blockierend.
+
+use ironcrab::ipc::{ExecutionFees, ExecutionPnl, ExecutionResult, MarketEvent, MarketEventKind};
+use ironcrab::storage::parquet_writer::{
+    parquet_file_name, ColumnSpec, ColumnType, ColumnValue, ParquetRecord, ParquetSinkConfig,
+    ParquetStream, ParquetWriterConfig, QueuedParquetWriter,
+};
+use parquet::basic::Repetition;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+/// 2025-01-01T00:00:00Z
+const DAY1_MS: u64 = 1_735_689_600_000;
+const DAY_MS: u64 = 86_400_000;
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+fn event(id: &str, ts_unix_ms: u64, kind: MarketEventKind) -> MarketEvent {
+    let mut e = MarketEvent::new(
+        "market-data",
+        "eval-a67",
+        "run-a67",
+        id.to_string(),
+        "geyser",
+        Some(1_000),
+        kind,
+    );
+    e.header.ts_unix_ms = ts_unix_ms;
+    e
+}
+
+fn pool_created(id: &str, ts_unix_ms: u64) -> MarketEvent {
+    event(
+        id,
+        ts_unix_ms,
+        MarketEventKind::PoolCreated {
+            pool_address: "Pool123".to_string(),
+            base_mint: "BaseMint".to_string(),
+            quote_mint: "QuoteMint".to_string(),
+            dex: "pump_amm".to_string(),
+            initial_liquidity_sol: Some(Decimal::from_str("85.123456789").unwrap()),
+        },
+    )
+}
+
+fn slot_update(id: &str, ts_unix_ms: u64) -> MarketEvent {
+    event(
+        id,
+        ts_unix_ms,
+        MarketEventKind::SlotUpdate { current_slot: 42 },
+    )
+}
+
+fn confirmed_result(id: &str, ts_unix_ms: u64) -> ExecutionResult {
+    let mut r = ExecutionResult::new_sent(
+        "execution-engine",
+        "eval-a67",
+        "run-a67",
+        id.to_string(),
+        "dec-a67".to_string(),
+        "intent-a67".to_string(),
+        "momentum-bot".to_string(),
+        Some("MintA67".to_string()),
+        Some("sig-a67".to_string()),
+        None,
+    )
+    .mark_confirmed(
+        1_001,
+        ExecutionFees {
+            network_fee_lamports: 5_000,
+            tip_lamports: 10_000,
+            compute_units: 120_000,
+        },
+        ExecutionPnl {
+            gross_lamports: 0,
+            net_lamports: -15_000,
+            decimals: 9,
+        },
+        300,
+    );
+    r.header.ts_unix_ms = ts_unix_ms;
+    r
+}
+
+fn assert_parquet_file(path: &Path) {
+    let bytes = fs::read(path).unwrap_or_else(|e| panic!("{path:?}: {e}"));
+    assert!(bytes.len() > 8, "{path:?} zu klein");
+    assert_eq!(&bytes[..4], PARQUET_MAGIC, "{path:?}: Header-Magic");
+    assert_eq!(
+        &bytes[bytes.len() - 4..],
+        PARQUET_MAGIC,
+        "{path:?}: Footer-Magic (Datei sauber geschlossen)"
+    );
+}
+
+/// Zurueckgelesene Parquet-Datei: Zeilen als Spalte → Feld, plus Footer-Metadaten.
+struct ParquetReadback {
+    rows: Vec<HashMap<String, Field>>,
+    row_groups: usize,
+    columns: Vec<(String, Repetition)>,
+}
+
+fn read_parquet(path: &Path) -> ParquetReadback {
+    assert_parquet_file(path);
+    let reader = SerializedFileReader::new(File::open(path).unwrap())
+        .unwrap_or_else(|e| panic!("{path:?}: kein lesbares Parquet: {e}"));
+    let metadata = reader.metadata();
+    let columns = metadata
+        .file_metadata()
+        .schema_descr()
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|f| (f.name().to_string(), f.get_basic_info().repetition()))
+        .collect();
+    let row_groups = metadata.num_row_groups();
+    let num_rows = metadata.file_metadata().num_rows();
+    let rows: Vec<HashMap<String, Field>> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| {
+            row.unwrap()
+                .get_column_iter()
+                .map(|(name, field)| (name.clone(), field.clone()))
+                .collect()
+        })
+        .collect();
+    assert_eq!(rows.len() as i64, num_rows, "{path:?}: Footer-Zeilenzahl");
+    ParquetReadback {
+        rows,
+        row_groups,
+        columns,
+    }
+}
+
+/// `DECIMAL(38, 18)` → exakter `rust_decimal::Decimal` (Big-Endian-Zweierkomplement).
+fn field_decimal(field: &Field) -> Decimal {
+    let Field::Decimal(d) = field else {
+        panic!("kein Decimal-Feld: {field:?}");
+    };
+    assert_eq!(d.precision(), 38);
+    assert_eq!(d.scale(), 18);
+    let data = d.data();
+    assert!(data.len() <= 16);
+    let fill = if data.first().is_some_and(|b| b & 0x80 != 0) {
+        0xff
+    } else {
+        0x00
+    };
+    let mut be = [fill; 16];
+    be[16 - data.len()..].copy_from_slice(data);
+    Decimal::from_i128_with_scale(i128::from_be_bytes(be), d.scale() as u32)
+}
+
+fn field_str(field: &Field) -> &str {
+    match field {
+        Field::Str(s) => s,
+        other => panic!("kein Utf8-Feld: {other:?}"),
+    }
+}
+
+/// Datei-Schema = `ParquetRecord::schema()` (Namen, REQUIRED/OPTIONAL); jedes gelesene Feld hat
+/// den physisch passenden Typ oder ist `Null` in einer nullable Spalte.
+fn assert_readback_matches_schema(readback: &ParquetReadback, schema: &[ColumnSpec]) {
+    let expected: Vec<(String, Repetition)> = schema
+        .iter()
+        .map(|c| {
+            let repetition = if c.nullable {
+                Repetition::OPTIONAL
+            } else {
+                Repetition::REQUIRED
+            };
+            (c.name.clone(), repetition)
+        })
+        .collect();
+    assert_eq!(readback.columns, expected, "Datei-Schema");
+
+    for row in &readback.rows {
+        for col in schema {
+            let field = row
+                .get(&col.name)
+                .unwrap_or_else(|| panic!("{} fehlt", col.name));
+            let typed = if matches!(field, Field::Null) {
+                col.nullable
+            } else if col.data_type == ColumnType::Utf8 {
+                matches!(field, Field::Str(_))
+            } else if col.data_type == ColumnType::UInt64 {
+                matches!(field, Field::ULong(_))
+            } else if col.data_type == ColumnType::UInt32 {
+                matches!(field, Field::UInt(_))
+            } else if col.data_type == ColumnType::Decimal {
+                matches!(field, Field::Decimal(_))
+            } else {
+                true
+            };
+            assert!(
+                typed,
+                "{}: {field:?} passt nicht zu {:?}",
+                col.name, col.data_type
+            );
+        }
+    }
+}
+
+/// Spalten-Schema: Header + Event-Kopf + typisierte, nullable Varianten-Spalten; Namen eindeutig.
+#[test]
+fn a67_market_event_schema_flattens_kind_into_typed_columns() {
+    let schema = MarketEvent::schema();
+    let mut names = HashSet::new();
+    for col in &schema {
+        assert!(
+            names.insert(col.name.clone()),
+            "Spalte doppelt: {}",
+            col.name
+        );
+    }
+    let col = |name: &str| {
+        schema
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("Spalte `{name}` fehlt"))
+    };
+
+    for (name, ty) in [
+        ("schema_version", ColumnType::UInt32),
+        ("ts_unix_ms", ColumnType::UInt64),
+        ("component", ColumnType::Utf8),
+        ("run_id", ColumnType::Utf8),
+        ("event_id", ColumnType::Utf8),
+        ("kind", ColumnType::Utf8),
+    ] {
+        assert_eq!(col(name).data_type, ty, "{name}");
+        assert!(!col(name).nullable, "{name} ist Pflicht");
+    }
+    for (name, ty) in [
+        ("pool_address", ColumnType::Utf8),
+        ("initial_liquidity_sol", ColumnType::Decimal),
+        ("current_slot", ColumnType::UInt64),
+        ("supply", ColumnType::UInt64),
+        ("signature", ColumnType::Utf8),
+    ] {
+        assert_eq!(col(name).data_type, ty, "{name}");
+        assert!(col(name).nullable, "Varianten-Spalte {name} ist nullable");
+    }
+}
+
+/// Zeile: `kind` = Varianten-Name, eigene Felder typisiert, fremde Varianten-Spalten `Null`;
+/// jede Zeilen-Spalte existiert im Schema mit passendem Typ.
+#[test]
+fn a67_market_event_row_is_typed_and_matches_schema() {
+    let schema = MarketEvent::schema();
+    let e = pool_created("evt-1", DAY1_MS);
+    let row = e.to_row();
+
+    assert_eq!(
+        row.get("kind"),
+        Some(&ColumnValue::Utf8("PoolCreated".to_string()))
+    );
+    assert_eq!(
+        row.get("pool_address"),
+        Some(&ColumnValue::Utf8("Pool123".to_string()))
+    );
+    assert_eq!(
+        row.get("initial_liquidity_sol"),
+        Some(&ColumnValue::Decimal(
+            Decimal::from_str("85.123456789").unwrap()
+        )),
+        "Decimal exakt, kein f64"
+    );
+    assert_eq!(row.get("ts_unix_ms"), Some(&ColumnValue::UInt64(DAY1_MS)));
+    assert_eq!(row.get("current_slot"), Some(&ColumnValue::Null));
+
+    let slot = slot_update("evt-2", DAY1_MS).to_row();
+    assert_eq!(slot.get("current_slot"), Some(&ColumnValue::UInt64(42)));
+    assert_eq!(slot.get("pool_address"), Some(&ColumnValue::Null));
+
+    for r in [&row, &slot] {
+        assert_eq!(r.len(), schema.len(), "jede Zeile hat alle Spalten");
+        for col in &schema {
+            let value = r
+                .get(&col.name)
+                .unwrap_or_else(|| panic!("{} fehlt", col.name));
+            match value.column_type() {
+                None => assert!(col.nullable, "{} ist nicht nullable", col.name),
+                Some(t) => assert_eq!(t, col.data_type, "{}", col.name),
+            }
+        }
+    }
+}
+
+/// Row-Groups: volle Gruppen werden geschrieben, `close` schreibt Rest + Footer.
+#[test]
+fn a67_row_groups_are_buffered_and_closed() {
+    let dir = tempdir().unwrap();
+    let config = ParquetWriterConfig::new(ParquetStream::MarketEvents)
+        .with_log_dir(dir.path())
+        .with_row_group_size(3);
+    let writer = QueuedParquetWriter::<MarketEvent>::spawn(config, 64).unwrap();
+    for i in 0..7 {
+        assert!(writer.try_write(pool_created(&format!("evt-{i}"), DAY1_MS + i)));
+    }
+    let stats = writer.close().unwrap();
+    assert_eq!(stats.rows, 7);
+    assert_eq!(stats.row_groups, 3, "3 + 3 + 1 Rest beim Close");
+    assert_eq!(stats.dropped, 0);
+
+    let path = dir
+        .path()
+        .join(parquet_file_name(ParquetStream::MarketEvents, DAY1_MS));
+    let readback = read_parquet(&path);
+    assert_eq!(readback.rows.len(), 7);
+    assert_eq!(readback.row_groups, 3);
+    assert_readback_matches_schema(&readback, &MarketEvent::schema());
+    for (i, row) in readback.rows.iter().enumerate() {
+        assert_eq!(field_str(&row["event_id"]), format!("evt-{i}"));
+        assert_eq!(field_str(&row["kind"]), "PoolCreated");
+        assert_eq!(field_str(&row["pool_address"]), "Pool123");
+        assert_eq!(row["ts_unix_ms"], Field::ULong(DAY1_MS + i as u64));
+        assert_eq!(row["schema_version"], Field::UInt(1));
+        assert_eq!(
+            field_decimal(&row["initial_liquidity_sol"]),
+            Decimal::from_str("85.123456789").unwrap(),
+            "Decimal exakt zurueckgelesen"
+        );
+        assert_eq!(row["current_slot"], Field::Null);
+    }
+}
+
+/// Tagesrotation (UTC) nach Record-Zeitstempel, gleiches Namensschema wie JSONL.
+#[test]
+fn a67_rotates_daily_by_record_timestamp() {
+    assert_eq!(
+        parquet_file_name(ParquetStream::MarketEvents, DAY1_MS),
+        "market_events-20250101.parquet"
+    );
+    assert_eq!(
+        parquet_file_name(ParquetStream::ExecutionResults, DAY1_MS + DAY_MS - 1),
+        "execution_results-20250101.parquet"
+    );
+    assert_eq!(
+        parquet_file_name(ParquetStream::MarketEvents, DAY1_MS + DAY_MS),
+        "market_events-20250102.parquet"
+    );
+
+    let dir = tempdir().unwrap();
+    let config = ParquetWriterConfig::new(ParquetStream::MarketEvents)
+        .with_log_dir(dir.path())
+        .with_row_group_size(100);
+    let writer = QueuedParquetWriter::<MarketEvent>::spawn(config, 64).unwrap();
+    assert!(writer.try_write(slot_update("d1", DAY1_MS + 10)));
+    assert!(writer.try_write(slot_update("d2", DAY1_MS + DAY_MS + 10)));
+    let stats = writer.close().unwrap();
+    assert_eq!(stats.files, 2);
+    for (file, id) in [
+        ("market_events-20250101.parquet", "d1"),
+        ("market_events-20250102.parquet", "d2"),
+    ] {
+        let readback = read_parquet(&dir.path().join(file));
+        assert_eq!(readback.rows.len(), 1, "{file}");
+        let row = &readback.rows[0];
+        assert_eq!(field_str(&row["event_id"]), id);
+        assert_eq!(field_str(&row["kind"]), "SlotUpdate");
+        assert_eq!(row["current_slot"], Field::ULong(42));
+        assert_eq!(row["initial_liquidity_sol"], Field::Null);
+    }
+}
+
+/// I-4b: Burst gegen kleine Queue — jeder `try_write` kehrt sofort zurueck,
+/// nicht angenommene Records werden gezaehlt.
+#[test]
+fn a67_try_write_never_blocks_and_counts_drops() {
+    const BURST: u64 = 20_000;
+    let dir = tempdir().unwrap();
+    let config = ParquetWriterConfig::new(ParquetStream::MarketEvents)
+        .with_log_dir(dir.path())
+        .with_row_group_size(1);
+    let writer = QueuedParquetWriter::<MarketEvent>::spawn(config, 4).unwrap();
+
+    let mut accepted = 0u64;
+    let mut slowest = Duration::ZERO;
+    let started = Instant::now();
+    for i in 0..BURST {
+        let e = pool_created(&format!("burst-{i}"), DAY1_MS + i);
+        let t = Instant::now();
+        if writer.try_write(e) {
+            accepted += 1;
+        }
+        slowest = slowest.max(t.elapsed());
+    }
+    assert!(
+        slowest < Duration::from_millis(50),
+        "try_write darf nicht auf den Writer warten (max {slowest:?})"
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    let stats = writer.close().unwrap();
+    assert_eq!(stats.rows, accepted);
+    assert_eq!(stats.rows + stats.dropped, BURST);
+}
+
+/// ExecutionResults: eigener Stream, gleiche Writer-Semantik.
+#[test]
+fn a67_execution_results_stream() {
+    let schema = ExecutionResult::schema();
+    for name in ["execution_id", "decision_id", "intent_id", "status"] {
+        assert!(schema.iter().any(|c| c.name == name), "Spalte {name}");
+    }
+    assert_eq!(ExecutionResult::STREAM, ParquetStream::ExecutionResults);
+    assert_eq!(MarketEvent::STREAM, ParquetStream::MarketEvents);
+
+    let dir = tempdir().unwrap();
+    let config = ParquetWriterConfig::new(ParquetStream::ExecutionResults)
+        .with_log_dir(dir.path())
+        .with_row_group_size(10);
+    let writer = QueuedParquetWriter::<ExecutionResult>::spawn(config, 16).unwrap();
+    assert!(writer.try_write(confirmed_result("exe-1", DAY1_MS)));
+    assert!(writer.try_write(confirmed_result("exe-2", DAY1_MS + 1)));
+    let stats = writer.close().unwrap();
+    assert_eq!(stats.rows, 2);
+    let readback = read_parquet(&dir.path().join("execution_results-20250101.parquet"));
+    assert_eq!(readback.rows.len(), 2);
+    assert_eq!(readback.row_groups, 1);
+    assert_readback_matches_schema(&readback, &schema);
+    for (row, id) in readback.rows.iter().zip(["exe-1", "exe-2"]) {
+        assert_eq!(field_str(&row["execution_id"]), id);
+        assert_eq!(field_str(&row["decision_id"]), "dec-a67");
+        assert_eq!(field_str(&row["intent_id"]), "intent-a67");
+        assert_eq!(field_str(&row["status"]), "confirmed");
+    }
+}
+
+/// Pro Stream einschaltbar; Default aus.
+#[test]
+fn a67_sink_enabled_per_stream() {
+    let default_cfg = ParquetSinkConfig::default();
+    assert!(!default_cfg.is_enabled(ParquetStream::MarketEvents));
+    assert!(!default_cfg.is_enabled(ParquetStream::ExecutionResults));
+
+    let cfg: ParquetSinkConfig =
+        serde_json::from_str(r#"{"market_events": true}"#).expect("Teil-Config");
+    assert!(cfg.is_enabled(ParquetStream::MarketEvents));
+    assert!(!cfg.is_enabled(ParquetStream::ExecutionResults));
+}
@@ -0,0 +1,362 @@
+//! Invariante A.70: Deterministischer Full-Pipeline-Replay mit virtueller Uhr (INVARIANTS.md A.70)
+//!
+//! Blackbox gegen `ironcrab::replay`:
+//! - Aufgezeichnete `MarketEvent`-JSONL laeuft durch Strategien (momentum-bot, arb-strategy) und
+//!   execution-engine, nicht nur Intents (A.10).
+//! - Zeit und Slot kommen ausschliesslich aus injizierter `VirtualClock` / `VirtualSlotSource`,
+//!   getrieben von `header.ts_unix_ms` bzw. Slot der Events.
+//! - Zwei Laeufe ueber dieselben Events → byte-identische Intent- und Decision-Streams
+//!   (INVARIANTS §C Replay Determinism).
+//! - Kein Wall-Clock-Read in den Decision-Pfaden (Source-Contract).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; kein NATS/RPC im Replay.
+
+use ironcrab::ipc::{MarketEvent, MarketEventKind};
+use ironcrab::replay::{
+    momentum_entry_recording, run_pipeline_replay, Clock, PipelineReplayConfig, ReplayStrategy,
+    SlotSource, VirtualClock, VirtualSlotSource, DECISION_PATH_SOURCES,
+    PIPELINE_REPLAY_DECISIONS_FILE, PIPELINE_REPLAY_INTENTS_FILE,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::tempdir;
+
+/// 2025-01-01T00:00:00Z – liegt weit vor jeder Wall-Clock eines Testlaufs.
+const T0_MS: u64 = 1_735_689_600_000;
+const RUN_ID: &str = "pipeline-replay-a70";
+const FIRST_SLOT: u64 = 300_000_000;
+/// `momentum_entry_recording` liegt vollstaendig vor `T0_MS + RECORDING_SPAN_MS`.
+const RECORDING_SPAN_MS: u64 = 60_000;
+const TAIL_FIRST_SLOT: u64 = FIRST_SLOT + 1_000;
+
+fn event(id: &str, ts_unix_ms: u64, slot: u64, kind: MarketEventKind) -> MarketEvent {
+    let mut e = MarketEvent::new(
+        "market-data",
+        "eval-a70",
+        "run-recorded",
+        id.to_string(),
+        "geyser",
+        Some(slot),
+        kind,
+    );
+    e.header.ts_unix_ms = ts_unix_ms;
+    e
+}
+
+/// Mitschnitt: kanonischer Momentum-Entry (Impl-Repo, A.70 Punkt 9), danach ruhige Slot-Updates.
+fn recorded_events() -> Vec<MarketEvent> {
+    let mut out = momentum_entry_recording(T0_MS, FIRST_SLOT);
+    for i in 0..20u64 {
+        let slot = TAIL_FIRST_SLOT + i;
+        out.push(event(
+            &format!("ev-slot-{i:02}"),
+            T0_MS + RECORDING_SPAN_MS + i * 400,
+            slot,
+            MarketEventKind::SlotUpdate { current_slot: slot },
+        ));
+    }
+    out
+}
+
+fn write_events(dir: &Path, events: &[MarketEvent]) -> PathBuf {
+    let path = dir.join("market_events-20250101.jsonl");
+    let body: String = events
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap() + "\n")
+        .collect();
+    fs::write(&path, body).unwrap();
+    path
+}
+
+fn replay(events: &Path, out: &Path) -> ironcrab::replay::PipelineReplayReport {
+    let config = PipelineReplayConfig::new(events.to_path_buf(), out.to_path_buf())
+        .with_strategies(vec![ReplayStrategy::Momentum, ReplayStrategy::Arbitrage])
+        .with_run_id(RUN_ID);
+    run_pipeline_replay(&config).expect("pipeline replay")
+}
+
+fn read_lines(path: &Path) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("{path:?}: {e}"))
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+/// Virtuelle Uhr: nur explizit vorgestellt, nie rueckwaerts; Klone teilen die Zeit.
+#[test]
+fn a70_virtual_clock_is_explicit_and_monotonic() {
+    let clock = VirtualClock::new(T0_MS);
+    let shared = clock.clone();
+    assert_eq!(clock.now_unix_ms(), T0_MS);
+
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(
+        clock.now_unix_ms(),
+        T0_MS,
+        "virtuelle Zeit laeuft nicht mit der Wall-Clock"
+    );
+
+    clock.advance_to(T0_MS + 1_000);
+    assert_eq!(
+        shared.now_unix_ms(),
+        T0_MS + 1_000,
+        "Klone teilen den Zustand"
+    );
+
+    clock.advance_to(T0_MS + 500);
+    assert_eq!(
+        clock.now_unix_ms(),
+        T0_MS + 1_000,
+        "Events mit aelterem ts drehen die Uhr nicht zurueck"
+    );
+
+    fn as_dyn(c: &dyn Clock) -> u64 {
+        c.now_unix_ms()
+    }
+    assert_eq!(as_dyn(&shared), T0_MS + 1_000);
+}
+
+/// Virtuelle Slot-Quelle: leer bis zum ersten Event, danach monoton (max gesehener Slot).
+#[test]
+fn a70_virtual_slot_source_is_monotonic() {
+    let slots = VirtualSlotSource::new();
+    let shared = slots.clone();
+    assert_eq!(slots.current_slot(), None);
+
+    slots.observe(300_000_010);
+    assert_eq!(shared.current_slot(), Some(300_000_010));
+    slots.observe(300_000_005);
+    assert_eq!(
+        slots.current_slot(),
+        Some(300_000_010),
+        "Slot laeuft nie rueckwaerts"
+    );
+
+    fn as_dyn(s: &dyn SlotSource) -> Option<u64> {
+        s.current_slot()
+    }
+    assert_eq!(as_dyn(&shared), Some(300_000_010));
+}
+
+/// Alle Events werden konsumiert; virtuelle Zeit endet beim letzten Event.
+#[test]
+fn a70_replay_consumes_all_events_under_virtual_time() {
+    let dir = tempdir().unwrap();
+    let events = recorded_events();
+    let input = write_events(dir.path(), &events);
+    let out = dir.path().join("out");
+
+    let report = replay(&input, &out);
+    assert_eq!(report.market_events, events.len() as u64);
+    assert_eq!(report.first_ts_unix_ms, T0_MS);
+    assert_eq!(
+        report.last_ts_unix_ms,
+        events.iter().map(|e| e.header.ts_unix_ms).max().unwrap()
+    );
+    assert_eq!(report.last_slot, Some(TAIL_FIRST_SLOT + 19));
+
+    let intents = read_lines(&out.join(PIPELINE_REPLAY_INTENTS_FILE));
+    let decisions = read_lines(&out.join(PIPELINE_REPLAY_DECISIONS_FILE));
+    assert!(
+        report.intents > 0,
+        "Momentum-Entry-Mitschnitt muss mindestens eine Intent erzeugen"
+    );
+    assert!(
+        intents.iter().any(|i| i["source"] == "momentum-bot"),
+        "mindestens eine Intent stammt von momentum-bot"
+    );
+    assert_eq!(report.intents, intents.len() as u64);
+    assert_eq!(report.decisions, decisions.len() as u64);
+    assert_eq!(
+        decisions.len(),
+        intents.len(),
+        "jede im Replay erzeugte Intent bekommt genau eine Decision"
+    );
+}
+
+/// Zeitstempel und Korrelation stammen aus dem Mitschnitt, nicht aus der Wall-Clock.
+#[test]
+fn a70_outputs_carry_virtual_timestamps_and_recorded_triggers() {
+    let dir = tempdir().unwrap();
+    let events = recorded_events();
+    let input = write_events(dir.path(), &events);
+    let out = dir.path().join("out");
+    replay(&input, &out);
+
+    let event_ts: HashSet<u64> = events.iter().map(|e| e.header.ts_unix_ms).collect();
+    let event_ids: HashSet<String> = events.iter().map(|e| e.event_id.clone()).collect();
+    let last_ts = *event_ts.iter().max().unwrap();
+
+    let intents = read_lines(&out.join(PIPELINE_REPLAY_INTENTS_FILE));
+    assert!(!intents.is_empty(), "ohne Intents waere der Test leer");
+    for intent in &intents {
+        let ts = intent["ts_unix_ms"].as_u64().unwrap();
+        assert!(
+            event_ts.contains(&ts),
+            "Intent-ts {ts} muss der virtuellen Zeit eines Events entsprechen"
+        );
+        assert_eq!(intent["run_id"], RUN_ID);
+        if let Some(trigger) = intent["trigger_event_id"].as_str() {
+            assert!(
+                event_ids.contains(trigger),
+                "trigger_event_id {trigger} nicht im Mitschnitt"
+            );
+        }
+    }
+    for decision in read_lines(&out.join(PIPELINE_REPLAY_DECISIONS_FILE)) {
+        let ts = decision["ts_unix_ms"].as_u64().unwrap();
+        assert!(
+            (T0_MS..=last_ts).contains(&ts),
+            "Decision-ts {ts} ausserhalb der virtuellen Zeit [{T0_MS}, {last_ts}]"
+        );
+        assert_eq!(decision["run_id"], RUN_ID);
+        for check in decision["checks"].as_array().into_iter().flatten() {
+            if check["check_name"] == "ttl_valid" {
+                assert_eq!(
+                    check["passed"], true,
+                    "TTL gegen Wall-Clock geprueft (Mitschnitt liegt in der Vergangenheit)"
+                );
+            }
+        }
+    }
+}
+
+/// Zwei Laeufe (mit Wall-Clock-Abstand) → byte-identische Intent- und Decision-Streams.
+#[test]
+fn a70_two_runs_produce_byte_identical_streams() {
+    let dir = tempdir().unwrap();
+    let input = write_events(dir.path(), &recorded_events());
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+
+    let report = replay(&input, &first);
+    assert!(
+        report.intents > 0 && report.decisions > 0,
+        "Vergleich leerer Streams beweist nichts"
+    );
+    std::thread::sleep(Duration::from_millis(1_100));
+    assert_eq!(replay(&input, &second), report);
+
+    for name in [PIPELINE_REPLAY_INTENTS_FILE, PIPELINE_REPLAY_DECISIONS_FILE] {
+        assert_eq!(
+            fs::read(first.join(name)).unwrap(),
+            fs::read(second.join(name)).unwrap(),
+            "{name} nicht byte-identisch"
+        );
+    }
+}
+
+/// Strategie-Auswahl: ohne Strategien entstehen keine Intents (Engine erzeugt selbst keine).
+#[test]
+fn a70_strategies_are_the_only_intent_sources() {
+    let dir = tempdir().unwrap();
+    let input = write_events(dir.path(), &recorded_events());
+    let out = dir.path().join("out");
+
+    let config = PipelineReplayConfig::new(input, out.clone())
+        .with_strategies(vec![])
+        .with_run_id(RUN_ID);
+    let report = run_pipeline_replay(&config).unwrap();
+    assert_eq!(report.intents, 0);
+    assert_eq!(report.decisions, 0);
+    assert!(read_lines(&out.join(PIPELINE_REPLAY_INTENTS_FILE)).is_empty());
+}
+
+fn iron_crab_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+}
+
+/// Golden-Mitschnitt im Impl-Repo: erzeugt mindestens eine Intent und ist reproduzierbar.
+#[test]
+fn a70_golden_market_event_fixture_replays_deterministically() {
+    let fixtures = iron_crab_root()
+        .join("tests")
+        .join("fixtures")
+        .join("pipeline_replay");
+    let Ok(entries) = fs::read_dir(&fixtures) else {
+        eprintln!("SKIP: {fixtures:?} nicht vorhanden (Geschwister-Layout fehlt)");
+        return;
+    };
+    let mut inputs: Vec<PathBuf> = entries
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.to_string_lossy().ends_with("_market_events.jsonl"))
+        .collect();
+    inputs.sort();
+    assert!(
+        !inputs.is_empty(),
+        "{fixtures:?}: mindestens ein *_market_events.jsonl erwartet"
+    );
+
+    for input in inputs {
+        let tmp = tempdir().unwrap();
+        let first = replay(&input, &tmp.path().join("first"));
+        let second = replay(&input, &tmp.path().join("second"));
+        assert!(
+            first.intents > 0,
+            "{input:?}: Mitschnitt erzeugt keine Intent"
+        );
+        assert_eq!(first, second, "{input:?}: Report nicht deterministisch");
+        for name in [PIPELINE_REPLAY_INTENTS_FILE, PIPELINE_REPLAY_DECISIONS_FILE] {
+            assert_eq!(
+                fs::read(tmp.path().join("first").join(name)).unwrap(),
+                fs::read(tmp.path().join("second").join(name)).unwrap(),
+                "{input:?}: {name} nicht byte-identisch"
+            );
+        }
+    }
+}
+
+/// Decision-Pfade, die `DECISION_PATH_SOURCES` mindestens enthalten muss (Impl-relative Pfade).
+/// `conditional_intents.rs` entscheidet ueber das Feuern mit der injizierten Zeit (A.59).
+const REQUIRED_DECISION_PATH_SOURCES: [&str; 4] = [
+    "src/bin/momentum_bot.rs",
+    "src/bin/arb_strategy.rs",
+    "src/bin/execution_engine.rs",
+    "src/execution/conditional_intents.rs",
+];
+
+/// Source-Contract: kein Wall-Clock-Read in den Decision-Pfaden.
+#[test]
+fn a70_decision_paths_have_no_wall_clock_reads() {
+    for required in REQUIRED_DECISION_PATH_SOURCES {
+        assert!(
+            DECISION_PATH_SOURCES.contains(&required),
+            "{required} fehlt in DECISION_PATH_SOURCES"
+        );
+    }
+
+    let root = iron_crab_root();
+    if !root.join("Cargo.toml").is_file() {
+        eprintln!("SKIP: {root:?} nicht vorhanden (Geschwister-Layout fehlt)");
+        return;
+    }
+    const FORBIDDEN: [&str; 5] = [
+        "SystemTime::now",
+        "Instant::now",
+        "Utc::now",
+        "Local::now",
+        "get_slot(",
+    ];
+    for rel in DECISION_PATH_SOURCES {
+        let path = root.join(rel);
+        let src = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path:?}: {e}"));
+        for (n, line) in src.lines().enumerate() {
+            let code = line.split("//").next().unwrap_or("");
+            for needle in FORBIDDEN {
+                assert!(
+                    !code.contains(needle),
+                    "{rel}:{}: `{needle}` im Decision-Pfad (Clock/SlotSource injizieren)",
+                    n + 1
+                );
+            }
+        }
+    }
+}
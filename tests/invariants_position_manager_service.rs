@@ -0,0 +1,499 @@
+//! Invariante A.58: position-manager als Positions-SOT (INVARIANTS.md A.58, Plan PositionAuthority)
+//!
+//! Blackbox gegen `ironcrab::position_manager` (Service-Kern des keyless Binaries `position-manager`):
+//! - Konsumiert confirmed `ExecutionResult`s und `WalletBalanceSnapshot`s, reduziert auf
+//!   `PositionState` (PA-1 Reducer).
+//! - Persistenz in JetStream KV (`POSITIONS`, Key = Mint); Restore ergibt denselben Zustand.
+//! - Konsumiert den Lot-Ledger-Stream (`LOT_LEDGER`, A.57) und haelt offene Lots pro Mint im selben
+//!   KV-Bucket (Key `lots.{mint}`); FIFO-Rechnung kommt aus `LotLedger`, nicht aus eigener Logik.
+//! - Request/Reply-Queries (`PositionQuery` → `PositionQueryReply`) auf `TOPIC_POSITION_QUERY`.
+//! - Versionierte Updates (`PositionUpdate.version` monoton pro Mint) auf `TOPIC_POSITION_UPDATES`.
+//! - Cold-Path-Reconciliation gegen On-Chain-Balances mit Divergenz-Report.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; position-manager bleibt keyless (I-1/I-2).
+
+use ironcrab::ipc::{
+    ExecutionFees, ExecutionPnl, ExecutionResult, ExplicitAmount, MarketEvent, MarketEventKind,
+    TradeSide,
+};
+use ironcrab::nats::topics::{TOPIC_POSITION_QUERY, TOPIC_POSITION_UPDATES};
+use ironcrab::position_authority::PositionStatus;
+use ironcrab::position_manager::{
+    LotRecord, PositionManager, PositionQuery, PositionQueryReply, PositionRecord, PositionUpdate,
+    UpdateSource, LOTS_KV_KEY_PREFIX, POSITIONS_KV_BUCKET,
+};
+use ironcrab::storage::lot_ledger::{BuyFill, LedgerEvent, LotLedger, SellFill};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const MINT: &str = "MintAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const MINT_2022: &str = "MintBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB";
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const SPL_TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+fn confirmed(exec_id: &str, mint: &str, side: TradeSide, token_raw: u64) -> ExecutionResult {
+    let sol = ExplicitAmount::new(10_000_000, 9);
+    let token = ExplicitAmount::new(token_raw, 6);
+    let (fill_in, fill_out) = match side {
+        TradeSide::Buy => (sol, token),
+        TradeSide::Sell => (token, sol),
+    };
+    ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-a58",
+        exec_id.to_string(),
+        format!("dec-{exec_id}"),
+        format!("intent-{exec_id}"),
+        "momentum-bot".to_string(),
+        Some(mint.to_string()),
+        Some(format!("sig-{exec_id}")),
+        None,
+    )
+    .mark_confirmed(
+        1_000,
+        ExecutionFees {
+            network_fee_lamports: 5_000,
+            tip_lamports: 0,
+            compute_units: 100_000,
+        },
+        ExecutionPnl {
+            gross_lamports: 0,
+            net_lamports: -5_000,
+            decimals: 9,
+        },
+        200,
+    )
+    .with_fills(Some(fill_in), Some(fill_out))
+    .with_side(side)
+}
+
+fn wallet_snapshot(mint: &str, balance_raw: u64, token_program: &str, slot: u64) -> MarketEvent {
+    MarketEvent::new(
+        "market-data",
+        "v0.1.0",
+        "run-a58",
+        format!("evt-wsnap-{mint}-{slot}"),
+        "geyser",
+        Some(slot),
+        MarketEventKind::WalletBalanceSnapshot {
+            mint: mint.to_string(),
+            balance_raw,
+            decimals: 6,
+            token_program: token_program.to_string(),
+        },
+    )
+}
+
+fn ledger_buy(exec_id: &str, amount_raw: u64, cost: u64, slot: u64) -> LedgerEvent {
+    LedgerEvent::Buy(BuyFill {
+        execution_id: exec_id.to_string(),
+        intent_id: format!("intent-{exec_id}"),
+        mint: MINT.to_string(),
+        pool: "PoolPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPPP".to_string(),
+        amount_raw,
+        cost_lamports: cost,
+        fees_lamports: 5_000,
+        entry_slot: slot,
+    })
+}
+
+fn ledger_sell(exec_id: &str, amount_raw: u64, proceeds: u64, slot: u64) -> LedgerEvent {
+    LedgerEvent::Sell(SellFill {
+        execution_id: exec_id.to_string(),
+        intent_id: format!("intent-{exec_id}"),
+        mint: MINT.to_string(),
+        amount_raw,
+        proceeds_lamports: proceeds,
+        fees_lamports: 5_000,
+        slot,
+    })
+}
+
+fn open_mints(pm: &PositionManager) -> Vec<String> {
+    match pm.handle_query(&PositionQuery::GetOpenPositions) {
+        PositionQueryReply::OpenPositions { positions } => {
+            let mut mints: Vec<_> = positions.into_iter().map(|p| p.mint).collect();
+            mints.sort();
+            mints
+        }
+        other => panic!("expected OpenPositions, got {other:?}"),
+    }
+}
+
+/// BUY + Scale-in + Teil-SELL laesst die Position offen; voller SELL schliesst sie.
+/// Jede Aenderung erzeugt ein Update mit monoton steigender Version pro Mint.
+#[test]
+fn a58_execution_results_drive_position_lifecycle() {
+    let mut pm = PositionManager::new();
+    let u1 = pm
+        .on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 100))
+        .expect("BUY erzeugt Update");
+    let u2 = pm
+        .on_execution_result(&confirmed("e2", MINT, TradeSide::Buy, 300))
+        .unwrap();
+    assert_eq!(u2.state.balance_raw, 400);
+    assert_eq!(u2.state.status, PositionStatus::Open);
+
+    let u3 = pm
+        .on_execution_result(&confirmed("e3", MINT, TradeSide::Sell, 150))
+        .unwrap();
+    assert_eq!(u3.state.balance_raw, 250);
+    assert_eq!(
+        u3.state.status,
+        PositionStatus::Open,
+        "Teil-SELL schliesst nicht"
+    );
+
+    let u4 = pm
+        .on_execution_result(&confirmed("e4", MINT, TradeSide::Sell, 250))
+        .unwrap();
+    assert_eq!(u4.state.balance_raw, 0);
+    assert_eq!(u4.state.status, PositionStatus::Closed);
+
+    let versions: Vec<u64> = [&u1, &u2, &u3, &u4].iter().map(|u| u.version).collect();
+    assert!(
+        versions.windows(2).all(|w| w[1] == w[0] + 1),
+        "{versions:?}"
+    );
+    assert!([&u1, &u2, &u3, &u4]
+        .iter()
+        .all(|u| u.source == UpdateSource::Execution && u.mint == MINT));
+}
+
+/// Nur confirmed Results zaehlen; Redelivery derselben `execution_id` aendert nichts.
+#[test]
+fn a58_only_confirmed_results_once() {
+    let mut pm = PositionManager::new();
+    let sent_only = ExecutionResult::new_sent(
+        "execution-engine",
+        "v0.1.0",
+        "run-a58",
+        "e0".to_string(),
+        "dec-e0".to_string(),
+        "intent-e0".to_string(),
+        "momentum-bot".to_string(),
+        Some(MINT.to_string()),
+        Some("sig-e0".to_string()),
+        None,
+    )
+    .with_side(TradeSide::Buy);
+    assert!(pm.on_execution_result(&sent_only).is_none());
+
+    let buy = confirmed("e1", MINT, TradeSide::Buy, 100);
+    assert!(pm.on_execution_result(&buy).is_some());
+    assert!(
+        pm.on_execution_result(&buy).is_none(),
+        "Duplicate ignoriert"
+    );
+    assert_eq!(open_mints(&pm), vec![MINT.to_string()]);
+}
+
+/// Wallet-Snapshots: stale wird ignoriert, Null schliesst, unbekannte Balance wird als ReconcileNeeded recovered,
+/// Token-2022-Program bleibt erhalten, SOL/WSOL ist keine Position.
+#[test]
+fn a58_wallet_snapshots_close_recover_and_ignore_wsol() {
+    let mut pm = PositionManager::new();
+    pm.on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 400));
+
+    // Snapshot aelter als der confirmed Fill (Slot 1_000) ist stale und wird ignoriert (A.48).
+    assert!(pm
+        .on_wallet_snapshot(&wallet_snapshot(MINT, 0, SPL_TOKEN, 900))
+        .is_none());
+
+    let closed = pm
+        .on_wallet_snapshot(&wallet_snapshot(MINT, 0, SPL_TOKEN, 2_010))
+        .unwrap();
+    assert_eq!(closed.state.status, PositionStatus::Closed);
+    assert_eq!(closed.source, UpdateSource::WalletSnapshot);
+
+    let recovered = pm
+        .on_wallet_snapshot(&wallet_snapshot(MINT_2022, 250, TOKEN_2022, 2_011))
+        .unwrap();
+    assert_eq!(recovered.state.balance_raw, 250);
+    assert_eq!(recovered.state.status, PositionStatus::ReconcileNeeded);
+    assert_eq!(recovered.state.token_program, TOKEN_2022);
+
+    assert!(pm
+        .on_wallet_snapshot(&wallet_snapshot(WSOL_MINT, 5_000_000_000, SPL_TOKEN, 2_012))
+        .is_none());
+    assert_eq!(open_mints(&pm), vec![MINT_2022.to_string()]);
+}
+
+/// Request/Reply: Queries und Replies sind JSON-roundtrip-faehig; unbekannte Mint → NotFound.
+#[test]
+fn a58_query_request_reply_contract() {
+    assert_eq!(TOPIC_POSITION_QUERY, "ironcrab.v1.position_query");
+    let mut pm = PositionManager::new();
+    pm.on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 400));
+
+    for query in [
+        PositionQuery::GetOpenPositions,
+        PositionQuery::GetPosition {
+            mint: MINT.to_string(),
+        },
+    ] {
+        let wire = serde_json::to_vec(&query).unwrap();
+        let parsed: PositionQuery = serde_json::from_slice(&wire).unwrap();
+        assert_eq!(parsed, query);
+        let reply = pm.handle_query(&parsed);
+        let reply_wire = serde_json::to_vec(&reply).unwrap();
+        let reply_parsed: PositionQueryReply = serde_json::from_slice(&reply_wire).unwrap();
+        assert_eq!(reply_parsed, reply);
+    }
+
+    match pm.handle_query(&PositionQuery::GetPosition {
+        mint: MINT.to_string(),
+    }) {
+        PositionQueryReply::Position { state } => assert_eq!(state.balance_raw, 400),
+        other => panic!("expected Position, got {other:?}"),
+    }
+    assert_eq!(
+        pm.handle_query(&PositionQuery::GetPosition {
+            mint: "unknown".to_string()
+        }),
+        PositionQueryReply::NotFound {
+            mint: "unknown".to_string()
+        }
+    );
+}
+
+/// JetStream KV: ein Record pro Mint; Restore ergibt denselben Zustand inkl. Versionen und
+/// Dedupe-Wissen (kein Doppelzaehlen nach Restart).
+#[test]
+fn a58_kv_records_restore_state() {
+    assert_eq!(POSITIONS_KV_BUCKET, "POSITIONS");
+    let mut pm = PositionManager::new();
+    pm.on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 400));
+    pm.on_execution_result(&confirmed("e2", MINT, TradeSide::Sell, 100));
+    pm.on_wallet_snapshot(&wallet_snapshot(MINT_2022, 250, TOKEN_2022, 2_011));
+
+    let records: Vec<PositionRecord> = pm.records();
+    assert_eq!(records.len(), 2);
+    let wire: Vec<(String, Vec<u8>)> = records
+        .iter()
+        .map(|r| (r.kv_key(), serde_json::to_vec(r).unwrap()))
+        .collect();
+    assert!(wire.iter().any(|(k, _)| k == MINT));
+
+    let restored_records: Vec<PositionRecord> = wire
+        .iter()
+        .map(|(_, v)| serde_json::from_slice(v).unwrap())
+        .collect();
+    let mut restored = PositionManager::restore(restored_records);
+    assert_eq!(open_mints(&restored), open_mints(&pm));
+    assert_eq!(
+        restored.handle_query(&PositionQuery::GetPosition {
+            mint: MINT.to_string()
+        }),
+        pm.handle_query(&PositionQuery::GetPosition {
+            mint: MINT.to_string()
+        })
+    );
+
+    // Redelivery eines bereits verarbeiteten Results nach Restart.
+    assert!(restored
+        .on_execution_result(&confirmed("e2", MINT, TradeSide::Sell, 100))
+        .is_none());
+    // Versionen laufen nach Restore weiter.
+    let next = restored
+        .on_execution_result(&confirmed("e3", MINT, TradeSide::Sell, 300))
+        .unwrap();
+    let before = pm.records().into_iter().find(|r| r.mint == MINT).unwrap();
+    assert_eq!(next.version, before.version + 1);
+}
+
+/// Lots aus dem Lot-Ledger-Stream: FIFO-Stand identisch zu `LotLedger` (A.57), per `GetLots`
+/// abfragbar, als `LotRecord` im KV (`lots.{mint}`) persistiert und nach Restore ohne
+/// Doppelverbrauch weitergefuehrt.
+#[test]
+fn a58_lots_from_ledger_stream_are_kept_in_kv_and_queryable() {
+    assert_eq!(LOTS_KV_KEY_PREFIX, "lots.");
+    let events = [
+        ledger_buy("e1", 1_000, 100_000_000, 100),
+        ledger_buy("e2", 500, 60_000_000, 110),
+        ledger_sell("e3", 1_200, 150_000_000, 120),
+    ];
+
+    let mut pm = PositionManager::new();
+    for event in &events {
+        let record = pm.on_ledger_event(event).expect("neues Ledger-Event");
+        assert_eq!(record.mint, MINT);
+        assert_eq!(record.kv_key(), format!("lots.{MINT}"));
+    }
+    assert!(
+        pm.on_ledger_event(&events[2]).is_none(),
+        "Redelivery derselben execution_id verbraucht nicht erneut"
+    );
+
+    let expected = LotLedger::replay(&events).open_lots(MINT);
+    assert_eq!(expected.len(), 1);
+    assert_eq!(expected[0].lot_id, "intent-e2");
+    assert_eq!(expected[0].remaining_raw, 300);
+
+    let query = PositionQuery::GetLots {
+        mint: MINT.to_string(),
+    };
+    let wire = serde_json::to_vec(&query).unwrap();
+    let parsed: PositionQuery = serde_json::from_slice(&wire).unwrap();
+    assert_eq!(parsed, query);
+    let reply = pm.handle_query(&parsed);
+    assert_eq!(
+        reply,
+        PositionQueryReply::Lots {
+            mint: MINT.to_string(),
+            lots: expected,
+        }
+    );
+    let reply_parsed: PositionQueryReply =
+        serde_json::from_slice(&serde_json::to_vec(&reply).unwrap()).unwrap();
+    assert_eq!(reply_parsed, reply);
+
+    assert_eq!(
+        pm.handle_query(&PositionQuery::GetLots {
+            mint: MINT_2022.to_string()
+        }),
+        PositionQueryReply::Lots {
+            mint: MINT_2022.to_string(),
+            lots: vec![],
+        },
+        "Mint ohne Lots → leere Liste"
+    );
+
+    // KV-Roundtrip: Positions- und Lot-Records liegen im selben Bucket unter disjunkten Keys.
+    let lot_records: Vec<LotRecord> = pm.lot_records();
+    assert_eq!(lot_records.len(), 1);
+    let lot_wire: Vec<(String, Vec<u8>)> = lot_records
+        .iter()
+        .map(|r| (r.kv_key(), serde_json::to_vec(r).unwrap()))
+        .collect();
+    let mut restored = PositionManager::restore(pm.records());
+    restored.restore_lots(
+        lot_wire
+            .iter()
+            .map(|(_, v)| serde_json::from_slice(v).unwrap())
+            .collect(),
+    );
+    assert_eq!(restored.handle_query(&query), reply);
+    assert!(restored.on_ledger_event(&events[2]).is_none());
+
+    // Weiterer SELL nach Restore verbraucht den Rest des FIFO-Lots.
+    let last = restored
+        .on_ledger_event(&ledger_sell("e4", 300, 40_000_000, 130))
+        .unwrap();
+    assert!(last.lots.is_empty());
+    assert_eq!(
+        restored.handle_query(&query),
+        PositionQueryReply::Lots {
+            mint: MINT.to_string(),
+            lots: vec![],
+        }
+    );
+}
+
+/// Cold-Path-Reconciliation: On-Chain gewinnt, Divergenzen werden berichtet und als
+/// `Reconciliation`-Updates publiziert; fehlender On-Chain-Eintrag = 0.
+#[test]
+fn a58_reconciliation_reports_and_corrects_divergence() {
+    let mut pm = PositionManager::new();
+    pm.on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 400));
+    pm.on_wallet_snapshot(&wallet_snapshot(MINT_2022, 250, TOKEN_2022, 2_011));
+
+    let onchain: HashMap<String, u64> = [(MINT.to_string(), 380)].into_iter().collect();
+    let report = pm.reconcile(&onchain, 5_000);
+
+    let mut divergences: Vec<(String, u64, u64)> = report
+        .divergences
+        .iter()
+        .map(|d| (d.mint.clone(), d.authority_raw, d.onchain_raw))
+        .collect();
+    divergences.sort();
+    assert_eq!(
+        divergences,
+        vec![
+            (MINT.to_string(), 400, 380),
+            (MINT_2022.to_string(), 250, 0)
+        ]
+    );
+    assert_eq!(report.updates.len(), 2);
+    assert!(report
+        .updates
+        .iter()
+        .all(|u| u.source == UpdateSource::Reconciliation));
+
+    assert_eq!(open_mints(&pm), vec![MINT.to_string()]);
+    match pm.handle_query(&PositionQuery::GetPosition {
+        mint: MINT.to_string(),
+    }) {
+        PositionQueryReply::Position { state } => {
+            assert_eq!(state.balance_raw, 380);
+            assert_eq!(state.status, PositionStatus::Open);
+        }
+        other => panic!("expected Position, got {other:?}"),
+    }
+
+    // Zweiter Lauf ohne Aenderung: keine Divergenz, keine Updates.
+    let again = pm.reconcile(&onchain, 5_100);
+    assert!(again.divergences.is_empty());
+    assert!(again.updates.is_empty());
+}
+
+/// Versioniertes Update-Topic; Update-Payload ist JSON-roundtrip-faehig.
+#[test]
+fn a58_position_update_wire_contract() {
+    assert_eq!(TOPIC_POSITION_UPDATES, "ironcrab.v1.position_updates");
+    let mut pm = PositionManager::new();
+    let update = pm
+        .on_execution_result(&confirmed("e1", MINT, TradeSide::Buy, 100))
+        .unwrap();
+    let json = serde_json::to_value(&update).unwrap();
+    assert_eq!(json["mint"], MINT);
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["source"], "execution");
+    let parsed: PositionUpdate = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, update);
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract (I-1/I-2): `position-manager` laedt keine Keys und signiert nicht.
+#[test]
+fn a58_position_manager_binary_is_keyless() {
+    let bin = iron_crab_bin_rs("position_manager");
+    if !bin.is_file() {
+        eprintln!("SKIP: position_manager.rs noch nicht vorhanden ({:?})", bin);
+        return;
+    }
+    let src = fs::read_to_string(&bin).unwrap_or_else(|e| panic!("read {bin:?}: {e}"));
+    let prod = src.split("#[cfg(test)]").next().unwrap_or_default();
+    for forbidden in [
+        "IRONCRAB_KEYPAIR_PATH",
+        "read_keypair",
+        "Keypair",
+        "sign_transaction",
+        "send_transaction",
+    ] {
+        assert!(
+            !prod.contains(forbidden),
+            "position_manager.rs darf `{forbidden}` nicht nutzen (keyless, I-1/I-2)"
+        );
+    }
+    assert!(
+        prod.contains("TOPIC_POSITION_QUERY") && prod.contains("TOPIC_POSITION_UPDATES"),
+        "position_manager.rs muss Query- und Update-Topic bedienen"
+    );
+    assert!(
+        prod.contains("LOT_LEDGER_STREAM"),
+        "position_manager.rs muss den Lot-Ledger-Stream konsumieren"
+    );
+}
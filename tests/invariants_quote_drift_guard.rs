@@ -0,0 +1,386 @@
+//! Invariante A.55: Quote-Drift-Guard unmittelbar vor dem Signieren (INVARIANTS.md A.55)
+//!
+//! Blackbox gegen `ironcrab::execution::quote_drift`:
+//! - Re-Quote aus aktuellem `LivePoolCache`-State via `quote_output_amount`.
+//! - Vergleich mit der Intent-Annahme (`expected_out`), Drift in bps (positiv = nachteilig).
+//! - Policy `Reject` → `QUOTE_DRIFT` ueber der Schwelle; Policy `TightenMinOut` → nachteilige
+//!   Drift bis `tighten_max_drift_bps` zieht min_out auf `current_out * (1 - tighten_slippage_bps)`
+//!   nach statt abzulehnen. min_out wird nie abgesenkt.
+//! - Drift-bps und Cache-Slot landen im DecisionRecord (`input_snapshots["quote_drift"]`).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Re-Quote nur aus Cache (kein RPC).
+
+use ironcrab::execution::live_pool_cache::{
+    create_shared_cache, CachedPoolState, PumpAmmState, SharedLivePoolCache,
+};
+use ironcrab::execution::quote_calculator::quote_output_amount;
+use ironcrab::execution::quote_drift::{
+    evaluate_quote_drift, DriftPolicy, QuoteDriftConfig, QuoteDriftInput, QuoteDriftVerdict,
+    QUOTE_DRIFT_CHECK_NAME, QUOTE_DRIFT_SNAPSHOT_KEY,
+};
+use ironcrab::ipc::{CheckResult, DecisionRecord, IntentOrigin, RejectReason, TradingRegime};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const AMOUNT_IN: u64 = 100_000_000; // 0.1 SOL
+
+fn sol_mint() -> Pubkey {
+    Pubkey::from_str(SOL_MINT).unwrap()
+}
+
+fn pump_amm(base_mint: Pubkey, base_reserve: u64, quote_reserve: u64) -> CachedPoolState {
+    CachedPoolState::PumpAmm(PumpAmmState {
+        base_mint,
+        quote_mint: sol_mint(),
+        pool_base_token_account: Pubkey::new_unique(),
+        pool_quote_token_account: Pubkey::new_unique(),
+        base_reserve: Some(base_reserve),
+        quote_reserve: Some(quote_reserve),
+        pool_accounts: (0..14).map(|_| Pubkey::new_unique()).collect(),
+        creator: None,
+    })
+}
+
+struct Fixture {
+    cache: SharedLivePoolCache,
+    pool: Pubkey,
+    mint: Pubkey,
+    /// Quote zum Zeitpunkt der Intent-Erzeugung (Strategy-Annahme).
+    expected_out: u64,
+}
+
+fn fixture() -> Fixture {
+    let cache = create_shared_cache();
+    let pool = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    cache.upsert(pool, pump_amm(mint, 1_000_000_000_000, 10_000_000_000), 500);
+    let state = cache.get(&pool).unwrap();
+    let expected_out = quote_output_amount(&state, AMOUNT_IN, &sol_mint()).unwrap();
+    Fixture {
+        cache,
+        pool,
+        mint,
+        expected_out,
+    }
+}
+
+fn input(f: &Fixture, min_out: u64) -> QuoteDriftInput {
+    QuoteDriftInput {
+        pool: f.pool,
+        input_mint: sol_mint(),
+        amount_in: AMOUNT_IN,
+        expected_out: f.expected_out,
+        min_out,
+        max_slippage_bps: 500,
+    }
+}
+
+fn config(policy: DriftPolicy) -> QuoteDriftConfig {
+    QuoteDriftConfig {
+        enabled: true,
+        max_drift_bps: 200,
+        policy,
+        tighten_max_drift_bps: 500,
+        tighten_slippage_bps: 50,
+    }
+}
+
+/// Intent-Annahme, die gegenueber dem aktuellen Cache-Quote genau `drift_bps` nachteilig ist
+/// (kleinstes `expected_out` mit `floor((E - C) * 10_000 / E) == drift_bps`).
+fn expected_for_drift(current_out: u64, drift_bps: u64) -> u64 {
+    (current_out * 10_000).div_ceil(10_000 - drift_bps)
+}
+
+/// Pool unveraendert → Drift 0, min_out unveraendert, Cache-Slot erfasst.
+#[test]
+fn a55_unchanged_pool_passes_with_zero_drift() {
+    let f = fixture();
+    let min_out = f.expected_out * 95 / 100;
+    let verdict = evaluate_quote_drift(&config(DriftPolicy::Reject), &input(&f, min_out), &f.cache);
+    assert_eq!(
+        verdict,
+        QuoteDriftVerdict::Pass {
+            drift_bps: 0,
+            current_out: f.expected_out,
+            cache_slot: 500,
+            min_out,
+        }
+    );
+}
+
+/// Nachteilige Drift ueber `tighten_max_drift_bps` → QUOTE_DRIFT, unter beiden Policies.
+#[test]
+fn a55_adverse_drift_over_hard_bound_rejects() {
+    let f = fixture();
+    // Token-Preis steigt (weniger Tokens pro SOL): quote_reserve +10 %.
+    f.cache.upsert(
+        f.pool,
+        pump_amm(f.mint, 1_000_000_000_000, 11_000_000_000),
+        510,
+    );
+    let current =
+        quote_output_amount(&f.cache.get(&f.pool).unwrap(), AMOUNT_IN, &sol_mint()).unwrap();
+    let expected_drift = ((f.expected_out - current) * 10_000 / f.expected_out) as i64;
+    assert!(expected_drift > 500);
+
+    for policy in [DriftPolicy::Reject, DriftPolicy::TightenMinOut] {
+        let verdict = evaluate_quote_drift(
+            &config(policy),
+            &input(&f, f.expected_out * 95 / 100),
+            &f.cache,
+        );
+        assert_eq!(
+            verdict,
+            QuoteDriftVerdict::Rejected {
+                drift_bps: expected_drift,
+                current_out: current,
+                cache_slot: 510,
+            }
+        );
+        assert_eq!(verdict.reject_reason(), Some(RejectReason::QuoteDrift));
+    }
+}
+
+/// Nachteilige Drift ueber `max_drift_bps`, aber innerhalb `tighten_max_drift_bps`: `Reject`
+/// lehnt ab, `TightenMinOut` zieht min_out auf den aktuellen Quote nach und laesst den Trade zu.
+#[test]
+fn a55_policies_differ_on_adverse_drift_within_bound() {
+    let f = fixture();
+    let current = f.expected_out;
+    let mut i = input(&f, 1);
+    i.expected_out = expected_for_drift(current, 300);
+    i.min_out = i.expected_out * (10_000 - 500) / 10_000;
+
+    let rejected = evaluate_quote_drift(&config(DriftPolicy::Reject), &i, &f.cache);
+    assert_eq!(
+        rejected,
+        QuoteDriftVerdict::Rejected {
+            drift_bps: 300,
+            current_out: current,
+            cache_slot: 500,
+        }
+    );
+    assert_eq!(rejected.reject_reason(), Some(RejectReason::QuoteDrift));
+
+    let tightened = evaluate_quote_drift(&config(DriftPolicy::TightenMinOut), &i, &f.cache);
+    let new_min_out = current * (10_000 - 50) / 10_000;
+    assert_eq!(
+        tightened,
+        QuoteDriftVerdict::Tightened {
+            drift_bps: 300,
+            current_out: current,
+            cache_slot: 500,
+            old_min_out: i.min_out,
+            new_min_out,
+        }
+    );
+    assert!(new_min_out > i.min_out, "min_out wird angehoben");
+    assert!(new_min_out <= current, "aktueller Quote erfuellt min_out");
+    assert_eq!(tightened.reject_reason(), None);
+    assert_eq!(tightened.effective_min_out(), Some(new_min_out));
+    assert!(tightened.to_check_result().passed);
+}
+
+/// `tighten_max_drift_bps` ist inklusiv: genau an der Grenze wird nachgezogen, 1 bps darueber
+/// lehnt auch `TightenMinOut` ab.
+#[test]
+fn a55_tighten_bound_is_inclusive() {
+    let f = fixture();
+    let current = f.expected_out;
+
+    let mut at = input(&f, 1);
+    at.expected_out = expected_for_drift(current, 500);
+    match evaluate_quote_drift(&config(DriftPolicy::TightenMinOut), &at, &f.cache) {
+        QuoteDriftVerdict::Tightened {
+            drift_bps,
+            new_min_out,
+            ..
+        } => {
+            assert_eq!(drift_bps, 500);
+            assert_eq!(new_min_out, current * (10_000 - 50) / 10_000);
+        }
+        other => panic!("expected Tightened, got {other:?}"),
+    }
+
+    let mut over = input(&f, 1);
+    over.expected_out = expected_for_drift(current, 501);
+    match evaluate_quote_drift(&config(DriftPolicy::TightenMinOut), &over, &f.cache) {
+        QuoteDriftVerdict::Rejected { drift_bps, .. } => assert_eq!(drift_bps, 501),
+        other => panic!("expected Rejected, got {other:?}"),
+    }
+}
+
+/// Drift genau an der Schwelle ist erlaubt, 1 bps darueber nicht (Schwelle inklusiv).
+#[test]
+fn a55_drift_at_threshold_passes() {
+    let f = fixture();
+    let current = f.expected_out;
+
+    // Kleinste Annahme mit floor((E - C) * 10_000 / E) == 200: E = ceil(C * 10_000 / 9_800).
+    let mut at = input(&f, 1);
+    at.expected_out = expected_for_drift(current, 200);
+    match evaluate_quote_drift(&config(DriftPolicy::Reject), &at, &f.cache) {
+        QuoteDriftVerdict::Pass {
+            drift_bps,
+            current_out,
+            ..
+        } => {
+            assert_eq!(drift_bps, 200);
+            assert_eq!(current_out, current);
+        }
+        other => panic!("expected Pass, got {other:?}"),
+    }
+
+    // Kleinste Annahme mit Drift 201.
+    let mut over = input(&f, 1);
+    over.expected_out = expected_for_drift(current, 201);
+    match evaluate_quote_drift(&config(DriftPolicy::Reject), &over, &f.cache) {
+        QuoteDriftVerdict::Rejected { drift_bps, .. } => assert_eq!(drift_bps, 201),
+        other => panic!("expected Rejected, got {other:?}"),
+    }
+}
+
+/// Policy TightenMinOut: guenstige Drift hebt min_out auf current_out * (1 - slippage).
+#[test]
+fn a55_tighten_policy_raises_min_out_on_favorable_drift() {
+    let f = fixture();
+    // Token billiger: quote_reserve -10 % → mehr Tokens pro SOL.
+    f.cache.upsert(
+        f.pool,
+        pump_amm(f.mint, 1_000_000_000_000, 9_000_000_000),
+        520,
+    );
+    let current =
+        quote_output_amount(&f.cache.get(&f.pool).unwrap(), AMOUNT_IN, &sol_mint()).unwrap();
+    let original_min_out = f.expected_out * 95 / 100;
+
+    let verdict = evaluate_quote_drift(
+        &config(DriftPolicy::TightenMinOut),
+        &input(&f, original_min_out),
+        &f.cache,
+    );
+    match verdict {
+        QuoteDriftVerdict::Tightened {
+            drift_bps,
+            current_out,
+            cache_slot,
+            old_min_out,
+            new_min_out,
+        } => {
+            assert!(drift_bps < 0, "guenstige Drift ist negativ");
+            assert_eq!(current_out, current);
+            assert_eq!(cache_slot, 520);
+            assert_eq!(old_min_out, original_min_out);
+            assert_eq!(new_min_out, current * (10_000 - 500) / 10_000);
+            assert!(new_min_out > old_min_out);
+        }
+        other => panic!("expected Tightened, got {other:?}"),
+    }
+}
+
+/// Policy TightenMinOut senkt min_out nie ab: nachteilige Drift innerhalb `max_drift_bps` behaelt
+/// das Original (Simulation/On-Chain-Slippage entscheidet, I-9) und wird als Pass mit Drift protokolliert.
+#[test]
+fn a55_tighten_policy_never_lowers_min_out() {
+    let f = fixture();
+    let current = f.expected_out;
+    let mut i = input(&f, current * 97 / 100);
+    // 100 bps nachteilig: current * (1 - 500 bps) laege unter dem Original-min_out.
+    i.expected_out = expected_for_drift(current, 100);
+    assert!(current * (10_000 - 500) / 10_000 < i.min_out);
+
+    let verdict = evaluate_quote_drift(&config(DriftPolicy::TightenMinOut), &i, &f.cache);
+    assert!(
+        matches!(verdict, QuoteDriftVerdict::Pass { .. }),
+        "{verdict:?}"
+    );
+    assert_eq!(verdict.effective_min_out(), Some(current * 97 / 100));
+    assert_eq!(verdict.drift_bps(), Some(100));
+    assert_eq!(verdict.reject_reason(), None);
+}
+
+/// Pool nicht im Cache → fail-closed mit eigenem Reason (kein RPC-Fallback).
+#[test]
+fn a55_missing_cache_state_is_fail_closed() {
+    let f = fixture();
+    let mut i = input(&f, 1);
+    i.pool = Pubkey::new_unique();
+    let verdict = evaluate_quote_drift(&config(DriftPolicy::Reject), &i, &f.cache);
+    assert_eq!(verdict, QuoteDriftVerdict::NoCacheState);
+    assert_eq!(
+        verdict.reject_reason(),
+        Some(RejectReason::QuoteDriftNoCacheState)
+    );
+}
+
+/// Default deaktiviert → Skipped; Golden Replays ohne `quote_drift`-Check unveraendert.
+#[test]
+fn a55_default_is_disabled() {
+    let f = fixture();
+    let cfg = QuoteDriftConfig::default();
+    assert!(!cfg.enabled);
+    assert_eq!(cfg.policy, DriftPolicy::Reject);
+    assert!(cfg.tighten_max_drift_bps >= cfg.max_drift_bps);
+    assert_eq!(
+        evaluate_quote_drift(&cfg, &input(&f, 1), &f.cache),
+        QuoteDriftVerdict::Skipped
+    );
+}
+
+/// Forensik: Drift-bps und Cache-Slot im DecisionRecord; Wire-Codes.
+#[test]
+fn a55_drift_and_slot_recorded_in_decision_record() {
+    let f = fixture();
+    f.cache.upsert(
+        f.pool,
+        pump_amm(f.mint, 1_000_000_000_000, 11_000_000_000),
+        540,
+    );
+    let drift_input = input(&f, 1);
+    let verdict = evaluate_quote_drift(&config(DriftPolicy::Reject), &drift_input, &f.cache);
+
+    let mut record = DecisionRecord::new_rejected(
+        "execution-engine",
+        "v0.1.0",
+        "run-a55",
+        "dec-a55".to_string(),
+        "intent-a55".to_string(),
+        "momentum-bot".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![verdict.to_check_result()],
+        "QUOTE_DRIFT".to_string(),
+    );
+    verdict.record_into(&drift_input, &mut record);
+
+    let check: &CheckResult = &record.checks[0];
+    assert_eq!(check.check_name, QUOTE_DRIFT_CHECK_NAME);
+    assert_eq!(QUOTE_DRIFT_CHECK_NAME, "quote_drift");
+    assert!(!check.passed);
+    assert_eq!(check.reason_code.as_deref(), Some("QUOTE_DRIFT"));
+
+    let json = serde_json::to_value(&record).unwrap();
+    let raw = &json["input_snapshots"][QUOTE_DRIFT_SNAPSHOT_KEY];
+    let snap: serde_json::Value = match raw.as_str() {
+        Some(s) => serde_json::from_str(s).unwrap(),
+        None => raw.clone(),
+    };
+    assert_eq!(snap["cache_slot"], 540);
+    assert_eq!(snap["drift_bps"], verdict.drift_bps().unwrap());
+    assert_eq!(snap["current_out"], verdict.current_out().unwrap());
+    assert_eq!(snap["expected_out"], f.expected_out);
+
+    for (reason, wire) in [
+        (RejectReason::QuoteDrift, "QUOTE_DRIFT"),
+        (
+            RejectReason::QuoteDriftNoCacheState,
+            "QUOTE_DRIFT_NO_CACHE_STATE",
+        ),
+    ] {
+        assert_eq!(
+            serde_json::to_string(&reason).unwrap(),
+            format!("\"{wire}\"")
+        );
+    }
+}
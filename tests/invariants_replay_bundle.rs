@@ -0,0 +1,657 @@
+//! Invariante A.69: Replay-Bundle-Export (INVARIANTS.md A.69, STORAGE_CONVENTIONS §6)
+//!
+//! Blackbox gegen `ironcrab::storage::replay_bundle`:
+//! - Auswahl per Zeitfenster oder Intent-IDs; Korrelation ueber
+//!   `trigger_event_id` → `event_id`, `intent_id`, `decision_id`.
+//! - Quellen sind Primaer- und Spill-Dateien (`{stem}_spill-YYYYMMDD.jsonl`, A.73).
+//! - Config-Snapshot zur `config_snapshot_id` der Decisions liegt byte-identisch als `config.toml` bei.
+//! - Bundle ist self-contained (relative Pfade), deterministisch und per SHA-256 pruefbar.
+//! - `execution-engine --replay-bundle DIR` konsumiert das Bundle direkt (Source-Contract).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Log-Root wird nur gelesen.
+
+use ironcrab::ipc::{
+    DecisionRecord, ExecutionFees, ExecutionPnl, ExecutionResult, ExplicitAmount, IntentOrigin,
+    IntentTier, MarketEvent, MarketEventKind, TradeIntent, TradeResources, TradeSide,
+    TradingRegime,
+};
+use ironcrab::storage::replay_bundle::{
+    export_bundle, verify_bundle, BundleError, BundleRequest, BundleSelector, BUNDLE_FILES,
+    BUNDLE_MANIFEST_FILE, CONFIG_SNAPSHOT_DIR,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+/// 2025-01-01T00:00:00Z
+const DAY1_MS: u64 = 1_735_689_600_000;
+const DAY_MS: u64 = 86_400_000;
+const CONFIG_ID: &str = "cfg-a69-001";
+const CONFIG_TOML: &str = "[risk]\nmax_position_lamports = 500000000\nmax_slippage_bps = 300\n";
+
+fn market_event(id: &str, ts_unix_ms: u64) -> MarketEvent {
+    let mut e = MarketEvent::new(
+        "market-data",
+        "eval-a69",
+        "run-a69",
+        id.to_string(),
+        "geyser",
+        Some(1_000),
+        MarketEventKind::SlotUpdate { current_slot: 42 },
+    );
+    e.header.ts_unix_ms = ts_unix_ms;
+    e
+}
+
+fn intent(id: &str, ts_unix_ms: u64, trigger_event_id: &str) -> Value {
+    let mut intent = TradeIntent::new(
+        "momentum-bot",
+        "eval-a69",
+        "run-a69",
+        id.to_string(),
+        "momentum-bot",
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(50_000_000, 9),
+        TradeResources {
+            input_mint: "So11111111111111111111111111111111111111112".to_string(),
+            output_mint: "MintA69".to_string(),
+            pools: vec!["PoolA69".to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    );
+    intent.header.ts_unix_ms = ts_unix_ms;
+    let mut v = serde_json::to_value(&intent).unwrap();
+    v["trigger_event_id"] = Value::String(trigger_event_id.to_string());
+    v
+}
+
+fn decision(id: &str, intent_id: &str, ts_unix_ms: u64, config_snapshot_id: &str) -> Value {
+    let mut record = DecisionRecord::new_rejected(
+        "execution-engine",
+        "eval-a69",
+        "run-a69",
+        id.to_string(),
+        intent_id.to_string(),
+        "momentum-bot".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![],
+        "RISK_MAX_SLIPPAGE".to_string(),
+    );
+    record.header.ts_unix_ms = ts_unix_ms;
+    let mut v = serde_json::to_value(&record).unwrap();
+    v["config_snapshot_id"] = Value::String(config_snapshot_id.to_string());
+    v
+}
+
+fn execution(id: &str, decision_id: &str, intent_id: &str, ts_unix_ms: u64) -> ExecutionResult {
+    let mut r = ExecutionResult::new_sent(
+        "execution-engine",
+        "eval-a69",
+        "run-a69",
+        id.to_string(),
+        decision_id.to_string(),
+        intent_id.to_string(),
+        "momentum-bot".to_string(),
+        Some("MintA69".to_string()),
+        Some(format!("sig-{id}")),
+        None,
+    )
+    .mark_confirmed(
+        1_001,
+        ExecutionFees {
+            network_fee_lamports: 5_000,
+            tip_lamports: 10_000,
+            compute_units: 120_000,
+        },
+        ExecutionPnl {
+            gross_lamports: 0,
+            net_lamports: -15_000,
+            decimals: 9,
+        },
+        300,
+    );
+    r.header.ts_unix_ms = ts_unix_ms;
+    r
+}
+
+fn append_jsonl<T: Serialize>(path: &Path, records: &[T]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut body = fs::read_to_string(path).unwrap_or_default();
+    for r in records {
+        body.push_str(&serde_json::to_string(r).unwrap());
+        body.push('\n');
+    }
+    fs::write(path, body).unwrap();
+}
+
+fn day_file(root: &Path, dir: &str, stem: &str, day: u64) -> PathBuf {
+    let date = ["20250101", "20250102", "20250103"][day as usize];
+    root.join(dir).join(format!("{stem}-{date}.jsonl"))
+}
+
+/// Log-Root mit drei korrelierten Ketten (A: Tag 1, B: Tag 2, C: Tag 3) plus Rauschen.
+///
+/// Kette X: MarketEvent `ev-x` → Intent `intent-x` → Decision `dec-x` → Execution `exec-x`.
+fn seed_log_root(root: &Path) {
+    for (day, chain) in [(0u64, "a"), (1, "b"), (2, "c")] {
+        let t = DAY1_MS + day * DAY_MS + 3_600_000;
+        append_jsonl(
+            &day_file(root, "market_events", "market_events", day),
+            &[
+                market_event(&format!("ev-{chain}"), t),
+                market_event(&format!("ev-noise-{chain}"), t + 5),
+            ],
+        );
+        append_jsonl(
+            &day_file(root, "intents", "trade_intents", day),
+            &[intent(
+                &format!("intent-{chain}"),
+                t + 10,
+                &format!("ev-{chain}"),
+            )],
+        );
+        append_jsonl(
+            &day_file(root, "decisions", "decision_records", day),
+            &[decision(
+                &format!("dec-{chain}"),
+                &format!("intent-{chain}"),
+                t + 20,
+                CONFIG_ID,
+            )],
+        );
+        append_jsonl(
+            &day_file(root, "executions", "execution_results", day),
+            &[execution(
+                &format!("exec-{chain}"),
+                &format!("dec-{chain}"),
+                &format!("intent-{chain}"),
+                t + 30,
+            )],
+        );
+    }
+    let snapshots = root.join(CONFIG_SNAPSHOT_DIR);
+    fs::create_dir_all(&snapshots).unwrap();
+    fs::write(snapshots.join(format!("{CONFIG_ID}.toml")), CONFIG_TOML).unwrap();
+    fs::write(
+        snapshots.join("cfg-a69-other.toml"),
+        "[risk]\nmax_slippage_bps = 1\n",
+    )
+    .unwrap();
+}
+
+fn spill_file(root: &Path, dir: &str, stem: &str, day: u64) -> PathBuf {
+    let date = ["20250101", "20250102", "20250103"][day as usize];
+    root.join(dir).join(format!("{stem}_spill-{date}.jsonl"))
+}
+
+fn read_lines(path: &Path) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("{path:?}: {e}"))
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+fn ids(path: &Path, field: &str) -> Vec<String> {
+    read_lines(path)
+        .iter()
+        .map(|v| v[field].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn intent_ids(list: &[&str]) -> BundleSelector {
+    BundleSelector::IntentIds(list.iter().map(|s| s.to_string()).collect())
+}
+
+fn request(root: &Path, out: &Path, selector: BundleSelector) -> BundleRequest {
+    BundleRequest {
+        log_root: root.to_path_buf(),
+        out_dir: out.to_path_buf(),
+        selector,
+    }
+}
+
+/// Bundle-Layout entspricht STORAGE_CONVENTIONS §6 plus Manifest.
+#[test]
+fn a69_bundle_layout_matches_storage_conventions() {
+    assert_eq!(
+        BUNDLE_FILES,
+        [
+            "market_events.jsonl",
+            "trade_intents.jsonl",
+            "decision_records.jsonl",
+            "execution_results.jsonl",
+            "config.toml",
+        ]
+    );
+    assert_eq!(BUNDLE_MANIFEST_FILE, "manifest.json");
+    assert_eq!(CONFIG_SNAPSHOT_DIR, "config_snapshots");
+
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+    let bundle = out.path().join("bundle");
+    export_bundle(&request(logs.path(), &bundle, intent_ids(&["intent-a"]))).unwrap();
+
+    let mut names: Vec<String> = fs::read_dir(&bundle)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    let mut expected: Vec<String> = BUNDLE_FILES.iter().map(|s| s.to_string()).collect();
+    expected.push(BUNDLE_MANIFEST_FILE.to_string());
+    expected.sort();
+    assert_eq!(names, expected, "keine weiteren Dateien im Bundle");
+}
+
+/// Auswahl per Intent-IDs: nur die korrelierten Ketten, kein Rauschen.
+#[test]
+fn a69_intent_id_selection_collects_correlated_chain() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+    let bundle = out.path().join("bundle");
+
+    let manifest = export_bundle(&request(
+        logs.path(),
+        &bundle,
+        intent_ids(&["intent-c", "intent-a"]),
+    ))
+    .unwrap();
+
+    assert_eq!(
+        ids(&bundle.join("trade_intents.jsonl"), "intent_id"),
+        ["intent-a", "intent-c"],
+        "chronologisch nach header.ts_unix_ms, nicht nach Eingabereihenfolge"
+    );
+    assert_eq!(
+        ids(&bundle.join("decision_records.jsonl"), "decision_id"),
+        ["dec-a", "dec-c"]
+    );
+    assert_eq!(
+        ids(&bundle.join("execution_results.jsonl"), "execution_id"),
+        ["exec-a", "exec-c"]
+    );
+    assert_eq!(
+        ids(&bundle.join("market_events.jsonl"), "event_id"),
+        ["ev-a", "ev-c"],
+        "nur per trigger_event_id referenzierte Events"
+    );
+
+    assert_eq!(manifest.counts.market_events, 2);
+    assert_eq!(manifest.counts.trade_intents, 2);
+    assert_eq!(manifest.counts.decision_records, 2);
+    assert_eq!(manifest.counts.execution_results, 2);
+    assert_eq!(manifest.config_snapshot_id, CONFIG_ID);
+}
+
+/// Auswahl per Zeitfenster `[from, to)`: Intents im Fenster + alle MarketEvents im Fenster.
+#[test]
+fn a69_time_range_selection_spans_rotated_files() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+    let bundle = out.path().join("bundle");
+
+    // Tag 2 komplett + Tag 3 bis kurz vor ev-c: nur Kette B, aber Events beider Tage im Fenster.
+    let from = DAY1_MS + DAY_MS;
+    let to = DAY1_MS + 2 * DAY_MS + 3_600_000;
+    let manifest = export_bundle(&request(
+        logs.path(),
+        &bundle,
+        BundleSelector::TimeRange {
+            from_unix_ms: from,
+            to_unix_ms: to,
+        },
+    ))
+    .unwrap();
+
+    assert_eq!(
+        ids(&bundle.join("trade_intents.jsonl"), "intent_id"),
+        ["intent-b"]
+    );
+    assert_eq!(
+        ids(&bundle.join("decision_records.jsonl"), "decision_id"),
+        ["dec-b"]
+    );
+    assert_eq!(
+        ids(&bundle.join("execution_results.jsonl"), "execution_id"),
+        ["exec-b"]
+    );
+    assert_eq!(
+        ids(&bundle.join("market_events.jsonl"), "event_id"),
+        ["ev-b", "ev-noise-b"],
+        "Zeitfenster-Modus nimmt alle MarketEvents im Fenster (Input fuer Replay)"
+    );
+    assert_eq!(manifest.counts.trade_intents, 1);
+
+    for v in read_lines(&bundle.join("market_events.jsonl")) {
+        let ts = v["ts_unix_ms"].as_u64().unwrap();
+        assert!((from..to).contains(&ts), "ts {ts} ausserhalb [from, to)");
+    }
+}
+
+/// Spill-Dateien (A.73) sind Quellen wie die Primaerdateien: Records aus einer Flut landen im
+/// Bundle, chronologisch mit den Primaer-Records zusammengefuehrt.
+#[test]
+fn a69_spill_file_records_are_included() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let root = logs.path();
+    // Kette S liegt komplett in Spill-Dateien, zeitlich mitten zwischen ev-a/ev-noise-a und intent-a.
+    let t = DAY1_MS + 3_600_000;
+    append_jsonl(
+        &spill_file(root, "market_events", "market_events", 0),
+        &[market_event("ev-s", t + 2)],
+    );
+    append_jsonl(
+        &spill_file(root, "intents", "trade_intents", 0),
+        &[intent("intent-s", t + 12, "ev-s")],
+    );
+    append_jsonl(
+        &spill_file(root, "decisions", "decision_records", 0),
+        &[decision("dec-s", "intent-s", t + 22, CONFIG_ID)],
+    );
+    append_jsonl(
+        &spill_file(root, "executions", "execution_results", 0),
+        &[execution("exec-s", "dec-s", "intent-s", t + 32)],
+    );
+
+    let out = tempdir().unwrap();
+    let by_id = out.path().join("by-id");
+    let manifest = export_bundle(&request(
+        root,
+        &by_id,
+        intent_ids(&["intent-a", "intent-s"]),
+    ))
+    .unwrap();
+    assert_eq!(
+        ids(&by_id.join("trade_intents.jsonl"), "intent_id"),
+        ["intent-a", "intent-s"]
+    );
+    assert_eq!(
+        ids(&by_id.join("decision_records.jsonl"), "decision_id"),
+        ["dec-a", "dec-s"]
+    );
+    assert_eq!(
+        ids(&by_id.join("execution_results.jsonl"), "execution_id"),
+        ["exec-a", "exec-s"]
+    );
+    assert_eq!(
+        ids(&by_id.join("market_events.jsonl"), "event_id"),
+        ["ev-a", "ev-s"]
+    );
+    assert_eq!(manifest.counts.trade_intents, 2);
+    assert_eq!(manifest.counts.execution_results, 2);
+
+    let by_range = out.path().join("by-range");
+    export_bundle(&request(
+        root,
+        &by_range,
+        BundleSelector::TimeRange {
+            from_unix_ms: DAY1_MS,
+            to_unix_ms: DAY1_MS + DAY_MS,
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        ids(&by_range.join("market_events.jsonl"), "event_id"),
+        ["ev-a", "ev-s", "ev-noise-a"],
+        "Spill-Events im Fenster, nach header.ts_unix_ms eingereiht"
+    );
+    assert_eq!(
+        ids(&by_range.join("trade_intents.jsonl"), "intent_id"),
+        ["intent-a", "intent-s"]
+    );
+    verify_bundle(&by_range).unwrap();
+}
+
+/// Config-Snapshot der `config_snapshot_id` liegt byte-identisch bei; fehlend oder mehrdeutig → Fehler.
+#[test]
+fn a69_config_snapshot_is_included_and_required() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+
+    let bundle = out.path().join("ok");
+    export_bundle(&request(logs.path(), &bundle, intent_ids(&["intent-b"]))).unwrap();
+    assert_eq!(
+        fs::read_to_string(bundle.join("config.toml")).unwrap(),
+        CONFIG_TOML
+    );
+
+    // Zweite Config-ID im selben Bundle → nicht reproduzierbar.
+    append_jsonl(
+        &day_file(logs.path(), "intents", "trade_intents", 0),
+        &[intent("intent-d", DAY1_MS + 7_200_000, "ev-a")],
+    );
+    append_jsonl(
+        &day_file(logs.path(), "decisions", "decision_records", 0),
+        &[decision(
+            "dec-d",
+            "intent-d",
+            DAY1_MS + 7_200_010,
+            "cfg-a69-other",
+        )],
+    );
+    let conflict = export_bundle(&request(
+        logs.path(),
+        &out.path().join("conflict"),
+        intent_ids(&["intent-a", "intent-d"]),
+    ));
+    match conflict {
+        Err(BundleError::ConflictingConfigSnapshots(found)) => {
+            let found: HashSet<String> = found.into_iter().collect();
+            assert_eq!(
+                found,
+                HashSet::from([CONFIG_ID.to_string(), "cfg-a69-other".to_string()])
+            );
+        }
+        other => panic!("expected ConflictingConfigSnapshots, got {other:?}"),
+    }
+
+    fs::remove_file(
+        logs.path()
+            .join(CONFIG_SNAPSHOT_DIR)
+            .join(format!("{CONFIG_ID}.toml")),
+    )
+    .unwrap();
+    let missing = export_bundle(&request(
+        logs.path(),
+        &out.path().join("missing"),
+        intent_ids(&["intent-b"]),
+    ));
+    assert!(
+        matches!(
+            &missing,
+            Err(BundleError::MissingConfigSnapshot { config_snapshot_id }) if config_snapshot_id == CONFIG_ID
+        ),
+        "got {missing:?}"
+    );
+    assert!(
+        !out.path()
+            .join("missing")
+            .join(BUNDLE_MANIFEST_FILE)
+            .exists(),
+        "fehlgeschlagener Export hinterlaesst kein gueltig aussehendes Bundle"
+    );
+}
+
+/// Leere Auswahl, unbekannte Intent-IDs und nicht leeres Zielverzeichnis sind Fehler.
+#[test]
+fn a69_invalid_selection_or_target_is_rejected() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+
+    let empty = export_bundle(&request(
+        logs.path(),
+        &out.path().join("empty"),
+        BundleSelector::TimeRange {
+            from_unix_ms: DAY1_MS - DAY_MS,
+            to_unix_ms: DAY1_MS,
+        },
+    ));
+    assert!(
+        matches!(empty, Err(BundleError::EmptySelection)),
+        "got {empty:?}"
+    );
+
+    let unknown = export_bundle(&request(
+        logs.path(),
+        &out.path().join("unknown"),
+        intent_ids(&["intent-a", "intent-zzz"]),
+    ));
+    match unknown {
+        Err(BundleError::UnknownIntentIds(missing)) => assert_eq!(missing, ["intent-zzz"]),
+        other => panic!("expected UnknownIntentIds, got {other:?}"),
+    }
+
+    let occupied = out.path().join("occupied");
+    fs::create_dir_all(&occupied).unwrap();
+    fs::write(occupied.join("keep.txt"), "nicht ueberschreiben").unwrap();
+    let res = export_bundle(&request(logs.path(), &occupied, intent_ids(&["intent-a"])));
+    assert!(
+        matches!(&res, Err(BundleError::OutputNotEmpty(p)) if p == &occupied),
+        "got {res:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(occupied.join("keep.txt")).unwrap(),
+        "nicht ueberschreiben"
+    );
+}
+
+/// Manifest: SHA-256 pro Datei, relative Namen; Manipulation wird erkannt.
+#[test]
+fn a69_manifest_checksums_detect_tampering() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+    let bundle = out.path().join("bundle");
+    let manifest =
+        export_bundle(&request(logs.path(), &bundle, intent_ids(&["intent-a"]))).unwrap();
+
+    let files: BTreeMap<String, String> = manifest.files.clone();
+    let mut expected: Vec<String> = BUNDLE_FILES.iter().map(|s| s.to_string()).collect();
+    expected.sort();
+    assert_eq!(
+        files.keys().cloned().collect::<Vec<_>>(),
+        expected,
+        "Checksumme fuer jede Bundle-Datei (Manifest selbst ausgenommen)"
+    );
+    for (name, sha) in &files {
+        assert!(!name.contains('/'), "relativer Dateiname erwartet: {name}");
+        assert_eq!(sha.len(), 64, "{name}: SHA-256 hex");
+        assert!(sha
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    }
+
+    let on_disk: Value =
+        serde_json::from_str(&fs::read_to_string(bundle.join(BUNDLE_MANIFEST_FILE)).unwrap())
+            .unwrap();
+    assert_eq!(on_disk["config_snapshot_id"], CONFIG_ID);
+    assert_eq!(
+        on_disk["files"]["config.toml"],
+        files["config.toml"].as_str()
+    );
+
+    assert_eq!(verify_bundle(&bundle).unwrap().files, files);
+
+    let decisions = bundle.join("decision_records.jsonl");
+    let mut tampered = fs::read_to_string(&decisions).unwrap();
+    tampered.push('\n');
+    fs::write(&decisions, tampered).unwrap();
+    match verify_bundle(&bundle) {
+        Err(BundleError::ChecksumMismatch { file }) => assert_eq!(file, "decision_records.jsonl"),
+        other => panic!("expected ChecksumMismatch, got {other:?}"),
+    }
+}
+
+/// Self-contained und deterministisch: verschiebbar, gleicher Input → byte-identisches Bundle.
+#[test]
+fn a69_bundle_is_relocatable_and_deterministic() {
+    let logs = tempdir().unwrap();
+    seed_log_root(logs.path());
+    let out = tempdir().unwrap();
+
+    let first = out.path().join("first");
+    let second = out.path().join("second");
+    let selector = || intent_ids(&["intent-a", "intent-b", "intent-c"]);
+    export_bundle(&request(logs.path(), &first, selector())).unwrap();
+    export_bundle(&request(logs.path(), &second, selector())).unwrap();
+    for name in BUNDLE_FILES.iter().chain([&BUNDLE_MANIFEST_FILE]) {
+        assert_eq!(
+            fs::read(first.join(name)).unwrap(),
+            fs::read(second.join(name)).unwrap(),
+            "{name} nicht deterministisch (keine Wall-Clock im Bundle)"
+        );
+    }
+
+    let manifest = fs::read_to_string(first.join(BUNDLE_MANIFEST_FILE)).unwrap();
+    let root = logs.path().to_string_lossy().into_owned();
+    assert!(
+        !manifest.contains(&root),
+        "Manifest darf keine absoluten Pfade des Log-Roots enthalten"
+    );
+
+    // Log-Root weg, Bundle verschoben → weiterhin gueltig.
+    drop(logs);
+    let moved = tempdir().unwrap();
+    let target = moved.path().join("moved");
+    fs::rename(&first, &target).unwrap();
+    let manifest = verify_bundle(&target).unwrap();
+    assert_eq!(manifest.counts.trade_intents, 3);
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: CLI exportiert Bundles; execution-engine liest sie direkt per `--replay-bundle`.
+#[test]
+fn a69_cli_export_and_engine_replay_bundle_entrypoints() {
+    let cli = iron_crab_bin_rs("ironcrab_cli");
+    let engine = iron_crab_bin_rs("execution_engine");
+    let (Ok(cli_src), Ok(engine_src)) = (fs::read_to_string(&cli), fs::read_to_string(&engine))
+    else {
+        eprintln!("SKIP: {cli:?} oder {engine:?} nicht vorhanden (Geschwister-Layout fehlt)");
+        return;
+    };
+
+    assert!(
+        cli_src.contains("export_bundle"),
+        "ironcrab-cli muss replay_bundle::export_bundle nutzen (keine zweite Korrelationslogik)"
+    );
+    for flag in ["--from", "--to", "--intent-id", "--out"] {
+        assert!(
+            cli_src.contains(flag),
+            "ironcrab-cli bundle export: Flag {flag} fehlt"
+        );
+    }
+    assert!(
+        engine_src.contains("replay-bundle"),
+        "execution-engine: --replay-bundle fehlt"
+    );
+    assert!(
+        engine_src.contains("verify_bundle"),
+        "execution-engine muss das Bundle vor dem Replay per verify_bundle pruefen"
+    );
+}
@@ -0,0 +1,235 @@
+//! Invariante A.53: Round-Trip-Sellability vor BUY (Honeypot-Check) (INVARIANTS.md A.53)
+//!
+//! Blackbox gegen `ironcrab::execution::round_trip_check`:
+//! - Gate: nur BUY, nur konfigurierte Sources/Regimes (Default: momentum-bot + EARLY).
+//! - Bewertung der sequentiellen BUY→SELL-Simulation: SELL-Sim-Fehler oder Verlust ueber
+//!   `max_round_trip_loss_bps` (nach Abzug der Fees) → eigener Reject-Code.
+//! - BUY-Leg-Fehler bleibt regulaerer `SIM_FAILED` (kein neuer Code fuer denselben Fall).
+//!
+//! Die Simulation selbst (RPC/simulateBundle) ist Impl-Sache; hier wird nur der
+//! oeffentliche Entscheidungs-Vertrag geprueft.
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests.
+
+use ironcrab::execution::round_trip_check::{
+    evaluate_round_trip, RoundTripConfig, RoundTripOutcome, RoundTripSimInputs,
+    ROUND_TRIP_CHECK_NAME,
+};
+use ironcrab::ipc::{
+    ExplicitAmount, IntentOrigin, IntentTier, RejectReason, SimulationResult, TradeIntent,
+    TradeResources, TradeSide, TradingRegime,
+};
+
+const ONE_SOL: u64 = 1_000_000_000;
+
+fn intent(source: &str, side: TradeSide, regime: TradingRegime) -> TradeIntent {
+    let mut resources = TradeResources::default();
+    resources
+        .pools
+        .push("Pool111111111111111111111111111111111111111".to_string());
+    resources.input_mint = "So11111111111111111111111111111111111111112".to_string();
+    resources.output_mint = "Mint111111111111111111111111111111111111111".to_string();
+    TradeIntent::new(
+        source,
+        "v0.1.0",
+        "run-a53",
+        "intent-a53".to_string(),
+        source,
+        IntentTier::Tier0,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(ONE_SOL / 10, 9),
+        resources,
+        0,
+        300,
+        side,
+        regime,
+    )
+}
+
+fn enabled_config() -> RoundTripConfig {
+    RoundTripConfig {
+        enabled: true,
+        max_round_trip_loss_bps: 300,
+        ..RoundTripConfig::default()
+    }
+}
+
+fn sim_ok() -> SimulationResult {
+    SimulationResult {
+        success: true,
+        error_code: None,
+        logs_preview: None,
+        compute_units_consumed: Some(120_000),
+    }
+}
+
+fn sim_err(code: &str) -> SimulationResult {
+    SimulationResult {
+        success: false,
+        error_code: Some(code.to_string()),
+        logs_preview: None,
+        compute_units_consumed: None,
+    }
+}
+
+fn inputs(sell_sim: SimulationResult, sell_out: Option<u64>) -> RoundTripSimInputs {
+    RoundTripSimInputs {
+        buy_in_lamports: ONE_SOL / 10,
+        buy_sim: sim_ok(),
+        expected_tokens_out: 3_500_000_000,
+        sell_sim,
+        sell_out_lamports: sell_out,
+        fees_lamports: 1_000_000,
+    }
+}
+
+/// Default: momentum-bot + EARLY; nur BUY; deaktiviert per Default.
+#[test]
+fn a53_gate_applies_only_to_configured_source_regime_and_buys() {
+    let default = RoundTripConfig::default();
+    assert!(!default.enabled, "Check ist optional und default aus");
+    assert!(!default.applies_to(&intent(
+        "momentum-bot",
+        TradeSide::Buy,
+        TradingRegime::Early
+    )));
+
+    let config = enabled_config();
+    assert!(config.applies_to(&intent(
+        "momentum-bot",
+        TradeSide::Buy,
+        TradingRegime::Early
+    )));
+    assert!(
+        !config.applies_to(&intent(
+            "momentum-bot",
+            TradeSide::Buy,
+            TradingRegime::Established
+        )),
+        "nur EARLY"
+    );
+    assert!(
+        !config.applies_to(&intent(
+            "arb-strategy",
+            TradeSide::Buy,
+            TradingRegime::Early
+        )),
+        "nur konfigurierte Sources"
+    );
+    assert!(
+        !config.applies_to(&intent(
+            "momentum-bot",
+            TradeSide::Sell,
+            TradingRegime::Early
+        )),
+        "SELL wird nie round-trip-geprueft"
+    );
+}
+
+/// Erweiterbare Gates: weitere Sources/Regimes explizit konfigurierbar.
+#[test]
+fn a53_gate_is_configurable() {
+    let config = RoundTripConfig {
+        sources: vec!["momentum-bot".to_string(), "arb-strategy".to_string()],
+        regimes: vec![TradingRegime::Early, TradingRegime::Established],
+        ..enabled_config()
+    };
+    assert!(config.applies_to(&intent(
+        "arb-strategy",
+        TradeSide::Buy,
+        TradingRegime::Established
+    )));
+}
+
+/// SELL-Simulation schlaegt fehl → ROUND_TRIP_SELL_FAILED mit Fehlercode.
+#[test]
+fn a53_sell_sim_failure_rejects_with_dedicated_reason() {
+    let outcome = evaluate_round_trip(&enabled_config(), &inputs(sim_err("Custom(6003)"), None));
+    assert_eq!(
+        outcome,
+        RoundTripOutcome::SellSimFailed {
+            error_code: Some("Custom(6003)".to_string())
+        }
+    );
+    assert_eq!(
+        outcome.reject_reason(),
+        Some(RejectReason::RoundTripSellFailed)
+    );
+}
+
+/// SELL-Sim "erfolgreich", aber ohne beobachtbaren Output → fail-closed wie SELL-Fehler.
+#[test]
+fn a53_sell_success_without_output_is_fail_closed() {
+    let outcome = evaluate_round_trip(&enabled_config(), &inputs(sim_ok(), None));
+    assert_eq!(
+        outcome.reject_reason(),
+        Some(RejectReason::RoundTripSellFailed)
+    );
+}
+
+/// Verlust nach Fees: (buy_in - fees - sell_out) / buy_in; Schwelle inklusiv.
+#[test]
+fn a53_loss_beyond_fees_threshold() {
+    let config = enabled_config(); // 300 bps
+    let buy_in = ONE_SOL / 10; // 100_000_000
+    let fees = 1_000_000;
+
+    // Genau 3 % Verlust ueber Fees → erlaubt.
+    let at_limit = buy_in - fees - 3_000_000;
+    let outcome = evaluate_round_trip(&config, &inputs(sim_ok(), Some(at_limit)));
+    assert_eq!(outcome, RoundTripOutcome::Passed { loss_bps: 300 });
+    assert_eq!(outcome.reject_reason(), None);
+
+    // 3.01 % → abgelehnt.
+    let above = at_limit - 10_000;
+    let outcome = evaluate_round_trip(&config, &inputs(sim_ok(), Some(above)));
+    assert_eq!(outcome, RoundTripOutcome::ExcessiveLoss { loss_bps: 301 });
+    assert_eq!(
+        outcome.reject_reason(),
+        Some(RejectReason::RoundTripExcessiveLoss)
+    );
+}
+
+/// Nur Fees verloren → 0 bps; Gewinn wird auf 0 gesaettigt.
+#[test]
+fn a53_fees_only_or_gain_is_zero_loss() {
+    let config = enabled_config();
+    let buy_in = ONE_SOL / 10;
+    let outcome = evaluate_round_trip(&config, &inputs(sim_ok(), Some(buy_in - 1_000_000)));
+    assert_eq!(outcome, RoundTripOutcome::Passed { loss_bps: 0 });
+    let outcome = evaluate_round_trip(&config, &inputs(sim_ok(), Some(buy_in * 2)));
+    assert_eq!(outcome, RoundTripOutcome::Passed { loss_bps: 0 });
+}
+
+/// BUY-Leg scheitert → regulaerer SIM_FAILED, SELL wird nicht bewertet.
+#[test]
+fn a53_buy_leg_failure_maps_to_sim_failed() {
+    let mut i = inputs(sim_ok(), Some(ONE_SOL));
+    i.buy_sim = sim_err("Custom(6001)");
+    let outcome = evaluate_round_trip(&enabled_config(), &i);
+    assert_eq!(
+        outcome,
+        RoundTripOutcome::BuyLegFailed {
+            error_code: Some("Custom(6001)".to_string())
+        }
+    );
+    assert_eq!(outcome.reject_reason(), Some(RejectReason::SimFailed));
+}
+
+/// Wire: Check-Name und Reject-Codes.
+#[test]
+fn a53_wire_contract() {
+    assert_eq!(ROUND_TRIP_CHECK_NAME, "round_trip_sell");
+    for (reason, wire) in [
+        (RejectReason::RoundTripSellFailed, "ROUND_TRIP_SELL_FAILED"),
+        (
+            RejectReason::RoundTripExcessiveLoss,
+            "ROUND_TRIP_EXCESSIVE_LOSS",
+        ),
+    ] {
+        let json = serde_json::to_string(&reason).unwrap();
+        assert_eq!(json, format!("\"{wire}\""));
+        let back: RejectReason = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, reason);
+    }
+}
@@ -0,0 +1,220 @@
+//! Invariante A.64: Versionierte IPC-Records mit Upcastern und Golden-Fixtures (INVARIANTS.md A.64)
+//!
+//! Blackbox gegen `ironcrab::ipc::schema_migration`:
+//! - Jede unterstuetzte `schema_version` von `MarketEvent`, `TradeIntent`, `DecisionRecord`,
+//!   `ExecutionResult` wird gelesen und auf den aktuellen In-Memory-Typ gehoben.
+//! - Der Encoder schreibt auf Wunsch die Vorgaengerversion (Rolling Deploy: alter Consumer liest
+//!   neuen Producer).
+//! - Golden-Fixtures jeder historischen Version liegen eingefroren unter
+//!   `tests/fixtures/schema/v{N}/{type}.json` (Capture: `tests/schema_fixture_capture.rs`).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; Fixtures werden nie nachtraeglich geaendert.
+
+use ironcrab::ipc::schema_migration::{
+    decode_versioned, encode_versioned, SchemaError, Versioned, CURRENT_SCHEMA_VERSION,
+    SUPPORTED_SCHEMA_VERSIONS,
+};
+use ironcrab::ipc::{DecisionRecord, ExecutionResult, MarketEvent, TradeIntent};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+const TYPES: [&str; 4] = [
+    "market_event",
+    "trade_intent",
+    "decision_record",
+    "execution_result",
+];
+
+/// Felder, die v2 gegenueber v1 additiv einfuehrt; der Downcast auf v1 entfernt sie.
+const V2_ONLY_FIELDS: [(&str, &[&str]); 2] = [
+    (
+        "execution_result",
+        &["side", "fill_source", "parent_intent_id", "slice_index"],
+    ),
+    ("decision_record", &["parent_intent_id", "slice_index"]),
+];
+
+fn fixture_path(version: u32, type_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("schema")
+        .join(format!("v{version}"))
+        .join(format!("{type_name}.json"))
+}
+
+fn read_fixture(version: u32, type_name: &str) -> String {
+    let path = fixture_path(version, type_name);
+    fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Golden-Fixture {path:?} fehlt ({e}); capturen mit \
+             IRONCRAB_CAPTURE_SCHEMA_FIXTURES=1 cargo test --test schema_fixture_capture -- --ignored"
+        )
+    })
+}
+
+/// Fixture lesen, auf den aktuellen Typ heben, in jede unterstuetzte Version zurueckschreiben
+/// und erneut lesen: Ergebnis identisch.
+fn assert_upcast_and_roundtrip<T>(type_name: &str)
+where
+    T: Versioned + serde::Serialize + PartialEq + std::fmt::Debug,
+{
+    assert_eq!(T::TYPE_NAME, type_name);
+    for &version in SUPPORTED_SCHEMA_VERSIONS {
+        let raw = read_fixture(version, type_name);
+        let fixture: Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(
+            fixture["schema_version"], version,
+            "{type_name} v{version}: Fixture traegt ihre eigene Version"
+        );
+
+        let decoded: T = decode_versioned(&raw)
+            .unwrap_or_else(|e| panic!("{type_name} v{version} nicht lesbar: {e:?}"));
+        let current = serde_json::to_value(&decoded).unwrap();
+        assert_eq!(
+            current["schema_version"], CURRENT_SCHEMA_VERSION,
+            "{type_name} v{version}: In-Memory-Typ ist immer die aktuelle Version"
+        );
+
+        for &target in SUPPORTED_SCHEMA_VERSIONS {
+            let encoded = encode_versioned(&decoded, target)
+                .unwrap_or_else(|e| panic!("{type_name} → v{target}: {e:?}"));
+            assert_eq!(encoded["schema_version"], target);
+            let again: T = decode_versioned(&encoded.to_string()).unwrap();
+            assert_eq!(
+                again, decoded,
+                "{type_name} v{version} → v{target} → aktuell verliert Information"
+            );
+        }
+    }
+}
+
+/// Mindestens aktuelle Version plus Vorgaenger (DoD §B: rueckwaertskompatibel fuer 1 Version).
+#[test]
+fn a64_supported_versions_cover_current_and_previous() {
+    assert_eq!(CURRENT_SCHEMA_VERSION, 2);
+    assert!(SUPPORTED_SCHEMA_VERSIONS.contains(&CURRENT_SCHEMA_VERSION));
+    assert!(SUPPORTED_SCHEMA_VERSIONS.contains(&(CURRENT_SCHEMA_VERSION - 1)));
+    assert!(
+        SUPPORTED_SCHEMA_VERSIONS.windows(2).all(|w| w[0] < w[1]),
+        "aufsteigend sortiert, ohne Duplikate"
+    );
+}
+
+/// Fuer jede unterstuetzte Version und jeden Typ existiert eine eingefrorene Fixture.
+#[test]
+fn a64_golden_fixture_exists_per_type_and_version() {
+    let missing: Vec<String> = SUPPORTED_SCHEMA_VERSIONS
+        .iter()
+        .flat_map(|&v| TYPES.iter().map(move |t| fixture_path(v, t)))
+        .filter(|p| !p.is_file())
+        .map(|p| p.display().to_string())
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "fehlende Golden-Fixtures (schema_fixture_capture): {missing:?}"
+    );
+}
+
+#[test]
+fn a64_market_event_upcasts_from_every_supported_version() {
+    assert_upcast_and_roundtrip::<MarketEvent>("market_event");
+}
+
+#[test]
+fn a64_trade_intent_upcasts_from_every_supported_version() {
+    assert_upcast_and_roundtrip::<TradeIntent>("trade_intent");
+}
+
+#[test]
+fn a64_decision_record_upcasts_from_every_supported_version() {
+    assert_upcast_and_roundtrip::<DecisionRecord>("decision_record");
+}
+
+#[test]
+fn a64_execution_result_upcasts_from_every_supported_version() {
+    assert_upcast_and_roundtrip::<ExecutionResult>("execution_result");
+}
+
+/// Rolling Deploy: Downcast auf die Vorgaengerversion entfernt v2-only-Felder; es bleiben nur
+/// Keys, die auch die eingefrorene v1-Fixture kennt.
+#[test]
+fn a64_encode_previous_version_strips_v2_only_fields() {
+    let previous = CURRENT_SCHEMA_VERSION - 1;
+    for (type_name, fields) in V2_ONLY_FIELDS {
+        let raw = read_fixture(CURRENT_SCHEMA_VERSION, type_name);
+        let encoded = match type_name {
+            "execution_result" => encode_versioned(
+                &decode_versioned::<ExecutionResult>(&raw).unwrap(),
+                previous,
+            ),
+            _ => encode_versioned(&decode_versioned::<DecisionRecord>(&raw).unwrap(), previous),
+        }
+        .unwrap();
+        let obj = encoded.as_object().expect("JSON-Objekt");
+        for field in fields {
+            assert!(
+                !obj.contains_key(*field),
+                "{type_name} v{previous} darf `{field}` nicht enthalten"
+            );
+        }
+        let v1_fixture: Value = serde_json::from_str(&read_fixture(previous, type_name)).unwrap();
+        let v1_keys: Vec<&String> = v1_fixture.as_object().unwrap().keys().collect();
+        for key in obj.keys() {
+            assert!(
+                v1_keys.contains(&key),
+                "{type_name} v{previous}: `{key}` ist kein v{previous}-Feld"
+            );
+        }
+    }
+}
+
+/// Unbekannte Versionen (zu alt, zu neu) und fehlende Version → typisierter Fehler, kein Raten.
+#[test]
+fn a64_unsupported_or_missing_version_is_rejected() {
+    let raw = read_fixture(CURRENT_SCHEMA_VERSION, "trade_intent");
+    let mut value: Value = serde_json::from_str(&raw).unwrap();
+
+    for bad in [0, CURRENT_SCHEMA_VERSION + 1] {
+        value["schema_version"] = bad.into();
+        match decode_versioned::<TradeIntent>(&value.to_string()) {
+            Err(SchemaError::UnsupportedVersion { type_name, version }) => {
+                assert_eq!(type_name, "trade_intent");
+                assert_eq!(version, bad);
+            }
+            other => panic!("v{bad} muss UnsupportedVersion liefern, war {other:?}"),
+        }
+    }
+
+    value.as_object_mut().unwrap().remove("schema_version");
+    assert!(matches!(
+        decode_versioned::<TradeIntent>(&value.to_string()),
+        Err(SchemaError::MissingVersion)
+    ));
+
+    let intent: TradeIntent = decode_versioned(&raw).unwrap();
+    assert!(matches!(
+        encode_versioned(&intent, CURRENT_SCHEMA_VERSION + 1),
+        Err(SchemaError::UnsupportedVersion { .. })
+    ));
+}
+
+/// Bestehende Golden-Replay-Intents (v1) bleiben ueber den Upcaster lesbar.
+#[test]
+fn a64_golden_replay_v1_intents_still_decode() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("golden_replays")
+        .join("normal_trade_simsucc_intents.jsonl");
+    let jsonl = fs::read_to_string(&path).unwrap();
+    let mut count = 0;
+    for line in jsonl.lines().filter(|l| !l.trim().is_empty()) {
+        let intent: TradeIntent = decode_versioned(line)
+            .unwrap_or_else(|e| panic!("Golden-Intent nicht lesbar: {e:?}\n{line}"));
+        assert_eq!(intent.header.schema_version, CURRENT_SCHEMA_VERSION);
+        count += 1;
+    }
+    assert!(count > 0, "Golden-Replay ohne Intents: {path:?}");
+}
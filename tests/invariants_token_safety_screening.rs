@@ -0,0 +1,392 @@
+//! Invariante A.52: Pre-Trade Token Safety Screening aus TokenMintInfo (INVARIANTS.md A.52)
+//!
+//! Blackbox gegen `ironcrab::execution::token_safety` + `MarketEventKind::TokenMintInfo`:
+//! - BUY auf Mints mit aktiver Freeze-Authority, aktiver Mint-Authority oder gefaehrlichen
+//!   Token-2022-Extensions (Permanent Delegate, Transfer Hook, Non-Transferable, hohe Transfer-Fee)
+//!   wird mit eigenem Reason-Code abgelehnt.
+//! - Per-Mint-Allowlist hebt gezielt einzelne Risiken auf.
+//! - SELL wird nie geblockt (Exit muss moeglich bleiben).
+//! - Momentum-Bot nutzt dasselbe Modul (Source-Contract gegen Sibling, SKIP ohne Checkout).
+//!
+//! STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests; keine private API.
+
+use ironcrab::execution::token_safety::{
+    TokenSafetyConfig, TokenSafetyRegistry, TokenSafetyRisk, TokenSafetyVerdict,
+    TOKEN_SAFETY_CHECK_NAME,
+};
+use ironcrab::ipc::{MarketEvent, MarketEventKind, RejectReason, Token2022Extension, TradeSide};
+use std::fs;
+use std::path::PathBuf;
+
+const SPL_TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const MINT: &str = "Mint111111111111111111111111111111111111111";
+const AUTHORITY: &str = "Auth111111111111111111111111111111111111111";
+
+fn mint_info(
+    token_program: &str,
+    mint_authority: Option<&str>,
+    freeze_authority: Option<&str>,
+    extensions: Vec<Token2022Extension>,
+) -> MarketEvent {
+    MarketEvent::new(
+        "market-data",
+        "v0.1.0",
+        "run-a52",
+        format!("evt-mint-{MINT}"),
+        "geyser",
+        Some(777),
+        MarketEventKind::TokenMintInfo {
+            mint: MINT.to_string(),
+            token_program: token_program.to_string(),
+            decimals: 6,
+            supply: 1_000_000_000_000_000,
+            mint_authority: mint_authority.map(str::to_string),
+            freeze_authority: freeze_authority.map(str::to_string),
+            extensions,
+        },
+    )
+}
+
+fn strict_config() -> TokenSafetyConfig {
+    TokenSafetyConfig {
+        enabled: true,
+        max_transfer_fee_bps: 100,
+        ..TokenSafetyConfig::default()
+    }
+}
+
+fn registry_with(event: MarketEvent, config: TokenSafetyConfig) -> TokenSafetyRegistry {
+    let mut registry = TokenSafetyRegistry::new(config);
+    registry.ingest(&event);
+    registry
+}
+
+fn rejected_reason(verdict: &TokenSafetyVerdict) -> RejectReason {
+    match verdict {
+        TokenSafetyVerdict::Rejected { primary, .. } => primary.clone(),
+        other => panic!("expected Rejected, got {other:?}"),
+    }
+}
+
+/// Saubere SPL-Mint (beide Authorities revoked, keine Extensions) → BUY erlaubt.
+#[test]
+fn a52_clean_spl_mint_is_allowed() {
+    let registry = registry_with(mint_info(SPL_TOKEN, None, None, vec![]), strict_config());
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Allowed
+    );
+}
+
+/// Jeder Risikofall hat einen eigenen Reason-Code.
+#[test]
+fn a52_each_risk_has_distinct_reason_code() {
+    let cases: Vec<(MarketEvent, RejectReason, &str)> = vec![
+        (
+            mint_info(SPL_TOKEN, None, Some(AUTHORITY), vec![]),
+            RejectReason::TokenFreezeAuthority,
+            "TOKEN_FREEZE_AUTHORITY",
+        ),
+        (
+            mint_info(SPL_TOKEN, Some(AUTHORITY), None, vec![]),
+            RejectReason::TokenMintAuthority,
+            "TOKEN_MINT_AUTHORITY",
+        ),
+        (
+            mint_info(
+                TOKEN_2022,
+                None,
+                None,
+                vec![Token2022Extension::PermanentDelegate {
+                    delegate: AUTHORITY.to_string(),
+                }],
+            ),
+            RejectReason::TokenPermanentDelegate,
+            "TOKEN_PERMANENT_DELEGATE",
+        ),
+        (
+            mint_info(
+                TOKEN_2022,
+                None,
+                None,
+                vec![Token2022Extension::TransferHook {
+                    program_id: AUTHORITY.to_string(),
+                }],
+            ),
+            RejectReason::TokenTransferHook,
+            "TOKEN_TRANSFER_HOOK",
+        ),
+        (
+            mint_info(
+                TOKEN_2022,
+                None,
+                None,
+                vec![Token2022Extension::NonTransferable],
+            ),
+            RejectReason::TokenNonTransferable,
+            "TOKEN_NON_TRANSFERABLE",
+        ),
+        (
+            mint_info(
+                TOKEN_2022,
+                None,
+                None,
+                vec![Token2022Extension::TransferFeeConfig {
+                    fee_bps: 500,
+                    max_fee_raw: u64::MAX,
+                }],
+            ),
+            RejectReason::TokenHighTransferFee,
+            "TOKEN_HIGH_TRANSFER_FEE",
+        ),
+    ];
+
+    for (event, expected, wire) in cases {
+        let registry = registry_with(event, strict_config());
+        let verdict = registry.screen(MINT, TradeSide::Buy);
+        assert_eq!(rejected_reason(&verdict), expected);
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            format!("\"{wire}\"")
+        );
+    }
+}
+
+/// Transfer-Fee genau an der Schwelle ist erlaubt, darueber nicht.
+#[test]
+fn a52_transfer_fee_threshold_is_inclusive() {
+    let at_limit = mint_info(
+        TOKEN_2022,
+        None,
+        None,
+        vec![Token2022Extension::TransferFeeConfig {
+            fee_bps: 100,
+            max_fee_raw: u64::MAX,
+        }],
+    );
+    let registry = registry_with(at_limit, strict_config());
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Allowed
+    );
+}
+
+/// Mehrere Risiken: alle werden gemeldet, primary folgt der dokumentierten Prioritaet
+/// (Freeze > Mint > PermanentDelegate > NonTransferable > TransferHook > HighTransferFee).
+#[test]
+fn a52_multiple_risks_reported_with_deterministic_primary() {
+    let event = mint_info(
+        TOKEN_2022,
+        Some(AUTHORITY),
+        Some(AUTHORITY),
+        vec![
+            Token2022Extension::TransferHook {
+                program_id: AUTHORITY.to_string(),
+            },
+            Token2022Extension::PermanentDelegate {
+                delegate: AUTHORITY.to_string(),
+            },
+        ],
+    );
+    let registry = registry_with(event, strict_config());
+    match registry.screen(MINT, TradeSide::Buy) {
+        TokenSafetyVerdict::Rejected { primary, risks } => {
+            assert_eq!(primary, RejectReason::TokenFreezeAuthority);
+            assert_eq!(
+                risks,
+                vec![
+                    TokenSafetyRisk::FreezeAuthority,
+                    TokenSafetyRisk::MintAuthority,
+                    TokenSafetyRisk::PermanentDelegate,
+                    TokenSafetyRisk::TransferHook,
+                ]
+            );
+        }
+        other => panic!("expected Rejected, got {other:?}"),
+    }
+}
+
+/// Per-Mint-Allowlist hebt nur die gelisteten Risiken auf.
+#[test]
+fn a52_per_mint_allowlist_overrides_only_listed_risks() {
+    let event = mint_info(SPL_TOKEN, Some(AUTHORITY), Some(AUTHORITY), vec![]);
+
+    let only_freeze = strict_config().with_allowlisted(MINT, &[TokenSafetyRisk::FreezeAuthority]);
+    let registry = registry_with(event.clone(), only_freeze);
+    assert_eq!(
+        rejected_reason(&registry.screen(MINT, TradeSide::Buy)),
+        RejectReason::TokenMintAuthority,
+        "Mint-Authority ist nicht allowlisted"
+    );
+
+    let both = strict_config().with_allowlisted(
+        MINT,
+        &[
+            TokenSafetyRisk::FreezeAuthority,
+            TokenSafetyRisk::MintAuthority,
+        ],
+    );
+    let registry = registry_with(event, both);
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Allowed
+    );
+}
+
+/// SELL wird nie durch Token-Safety geblockt.
+#[test]
+fn a52_sell_is_never_blocked() {
+    let event = mint_info(
+        TOKEN_2022,
+        Some(AUTHORITY),
+        Some(AUTHORITY),
+        vec![Token2022Extension::NonTransferable],
+    );
+    let registry = registry_with(event, strict_config());
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Sell),
+        TokenSafetyVerdict::Allowed
+    );
+}
+
+/// Ohne TokenMintInfo ist das Screening fail-closed (eigener Reason-Code).
+#[test]
+fn a52_missing_mint_info_rejects_buy() {
+    let registry = TokenSafetyRegistry::new(strict_config());
+    assert_eq!(
+        rejected_reason(&registry.screen(MINT, TradeSide::Buy)),
+        RejectReason::TokenMintInfoMissing
+    );
+    assert_eq!(
+        serde_json::to_string(&RejectReason::TokenMintInfoMissing).unwrap(),
+        "\"TOKEN_MINT_INFO_MISSING\""
+    );
+}
+
+/// Default ist deaktiviert → Golden Replays ohne `token_safety`-Check bleiben unveraendert.
+#[test]
+fn a52_default_config_is_disabled() {
+    let config = TokenSafetyConfig::default();
+    assert!(!config.enabled);
+    let registry = registry_with(
+        mint_info(SPL_TOKEN, Some(AUTHORITY), Some(AUTHORITY), vec![]),
+        config,
+    );
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Allowed
+    );
+    assert_eq!(TOKEN_SAFETY_CHECK_NAME, "token_safety");
+}
+
+/// Neueres TokenMintInfo ersetzt aelteres (z. B. Authority revoked nach Launch).
+#[test]
+fn a52_latest_mint_info_wins() {
+    let mut registry = TokenSafetyRegistry::new(strict_config());
+    registry.ingest(&mint_info(SPL_TOKEN, Some(AUTHORITY), None, vec![]));
+    assert!(matches!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Rejected { .. }
+    ));
+    registry.ingest(&mint_info(SPL_TOKEN, None, None, vec![]));
+    assert_eq!(
+        registry.screen(MINT, TradeSide::Buy),
+        TokenSafetyVerdict::Allowed
+    );
+}
+
+/// Schema: `extensions` ist additiv; aeltere JSONL-Records ohne Feld bleiben lesbar.
+#[test]
+fn a52_token_mint_info_extensions_backward_compatible() {
+    // Legacy-Record: aktuelles Event serialisieren und `extensions` entfernen.
+    let mut legacy = serde_json::to_value(mint_info(SPL_TOKEN, None, None, vec![])).unwrap();
+    strip_key(&mut legacy, "extensions");
+    let parsed: MarketEvent = serde_json::from_value(legacy).expect("legacy TokenMintInfo");
+    match parsed.kind {
+        MarketEventKind::TokenMintInfo { extensions, .. } => assert!(extensions.is_empty()),
+        other => panic!("expected TokenMintInfo, got {other:?}"),
+    }
+
+    let event = mint_info(
+        TOKEN_2022,
+        None,
+        None,
+        vec![Token2022Extension::TransferFeeConfig {
+            fee_bps: 250,
+            max_fee_raw: 1_000,
+        }],
+    );
+    let json = serde_json::to_string(&event).unwrap();
+    let back: MarketEvent = serde_json::from_str(&json).unwrap();
+    match back.kind {
+        MarketEventKind::TokenMintInfo { extensions, .. } => assert_eq!(
+            extensions,
+            vec![Token2022Extension::TransferFeeConfig {
+                fee_bps: 250,
+                max_fee_raw: 1_000,
+            }]
+        ),
+        other => panic!("expected TokenMintInfo, got {other:?}"),
+    }
+}
+
+fn strip_key(value: &mut serde_json::Value, key: &str) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.remove(key);
+            map.values_mut().for_each(|v| strip_key(v, key));
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| strip_key(v, key)),
+        _ => {}
+    }
+}
+
+fn iron_crab_bin_rs(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("parent of manifest")
+        .join("Iron_crab")
+        .join("src")
+        .join("bin")
+        .join(format!("{name}.rs"))
+}
+
+/// Source-Contract: execution-engine und momentum-bot nutzen dasselbe `token_safety`-Modul
+/// (kein zweites, divergierendes Screening im Bot).
+#[test]
+fn a52_engine_and_momentum_share_token_safety_module() {
+    let engine = iron_crab_bin_rs("execution_engine");
+    let momentum = iron_crab_bin_rs("momentum_bot");
+    if !engine.is_file() || !momentum.is_file() {
+        eprintln!("SKIP: Iron_crab Sibling-Checkout fehlt ({:?})", engine);
+        return;
+    }
+    let sources: Vec<(PathBuf, String)> = [engine, momentum]
+        .into_iter()
+        .map(|path| {
+            let src = fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {path:?}: {e}"));
+            let prod = src
+                .split("#[cfg(test)]")
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            (path, prod)
+        })
+        .collect();
+    // SKIP nur, solange noch keines der beiden Binaries verdrahtet ist. Ist eines verdrahtet,
+    // muessen beide dieselbe Registry nutzen.
+    if !sources
+        .iter()
+        .any(|(_, prod)| prod.contains("token_safety"))
+    {
+        eprintln!("SKIP: token_safety noch in keinem Binary verdrahtet");
+        return;
+    }
+    for (path, prod) in &sources {
+        assert!(
+            prod.contains("TokenSafetyRegistry"),
+            "{path:?} muss TokenSafetyRegistry aus ironcrab::execution::token_safety nutzen"
+        );
+    }
+}
@@ -83,6 +83,7 @@ fn market_event_token_mint_info_roundtrip() {
             supply: 1_000_000_000,
             mint_authority: None,
             freeze_authority: Some("FreezeAuth1111111111111111111111111111111".to_string()),
+            extensions: Vec::new(),
         },
     );
 
@@ -101,6 +102,7 @@ fn market_event_token_mint_info_roundtrip() {
             supply,
             mint_authority,
             freeze_authority,
+            extensions,
         } => {
             assert_eq!(mint, "So11111111111111111111111111111111111111112");
             assert_eq!(token_program, "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
@@ -111,6 +113,7 @@ fn market_event_token_mint_info_roundtrip() {
                 freeze_authority.as_deref(),
                 Some("FreezeAuth1111111111111111111111111111111")
             );
+            assert!(extensions.is_empty());
         }
         other => panic!("expected TokenMintInfo, got: {other:?}"),
     }
@@ -0,0 +1,184 @@
+//! Schema-Fixtures einfrieren (INVARIANTS.md A.64)
+//!
+//! Erzeugt pro IPC-Typ eine deterministische Golden-Fixture fuer die `schema_version`, die der
+//! gepinnte `ironcrab`-Rev schreibt: `tests/fixtures/schema/v{N}/{type}.json`.
+//! Vorhandene Dateien werden nie ueberschrieben (historische Versionen sind eingefroren).
+//!
+//! Ablauf bei jedem Schema-Bump: VOR dem Rev-Bump einmal mit altem Rev laufen lassen (fehlende
+//! Fixtures der alten Version), NACH dem Rev-Bump erneut (Fixtures der neuen Version):
+//!
+//! ```bash
+//! IRONCRAB_CAPTURE_SCHEMA_FIXTURES=1 cargo test --test schema_fixture_capture -- --ignored
+//! ```
+//!
+//! Nutzt nur API, die es seit v1 gibt. STOP-CHECK (AGENTS.md): nur Eval-Repo; nur Tests.
+
+use ironcrab::ipc::{
+    CheckResult, DecisionRecord, ExecutionFees, ExecutionPnl, ExecutionResult, ExplicitAmount,
+    IntentOrigin, IntentTier, MarketEvent, MarketEventKind, SimulationResult, TradeIntent,
+    TradeResources, TradeSide, TradingRegime,
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+const FIXED_TS_MS: u64 = 1_735_000_000_000;
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const MINT: &str = "SchemaMint111111111111111111111111111111111";
+const POOL: &str = "SchemaPool111111111111111111111111111111111";
+
+fn schema_dir(version: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("schema")
+        .join(format!("v{version}"))
+}
+
+fn market_event() -> MarketEvent {
+    let mut event = MarketEvent::new(
+        "market-data",
+        "schema-fixture",
+        "run-schema",
+        "evt-schema-001".to_string(),
+        "geyser",
+        Some(300_000_000),
+        MarketEventKind::PoolCreated {
+            pool_address: POOL.to_string(),
+            base_mint: MINT.to_string(),
+            quote_mint: WSOL_MINT.to_string(),
+            dex: "pump_amm".to_string(),
+            initial_liquidity_sol: Some(Decimal::from(85)),
+        },
+    );
+    event.header.ts_unix_ms = FIXED_TS_MS;
+    event
+}
+
+fn trade_intent() -> TradeIntent {
+    let mut intent = TradeIntent::new(
+        "momentum-bot",
+        "schema-fixture",
+        "run-schema",
+        "intent-schema-001".to_string(),
+        "momentum-bot",
+        IntentTier::Tier1,
+        IntentOrigin::StrategyA,
+        ExplicitAmount::new(50_000_000, 9),
+        TradeResources {
+            input_mint: WSOL_MINT.to_string(),
+            output_mint: MINT.to_string(),
+            pools: vec![POOL.to_string()],
+            accounts: vec![],
+            token_program: None,
+        },
+        0,
+        5_000,
+        TradeSide::Buy,
+        TradingRegime::Early,
+    );
+    intent.header.ts_unix_ms = FIXED_TS_MS;
+    intent
+}
+
+fn decision_record() -> DecisionRecord {
+    let mut record = DecisionRecord::new_sim_failed(
+        "execution-engine",
+        "schema-fixture",
+        "run-schema",
+        "dec-schema-001".to_string(),
+        "intent-schema-001".to_string(),
+        "momentum-bot".to_string(),
+        IntentOrigin::StrategyA,
+        TradingRegime::Early,
+        vec![
+            CheckResult {
+                check_name: "idempotency".to_string(),
+                passed: true,
+                reason_code: None,
+                details: None,
+            },
+            CheckResult {
+                check_name: "simulation".to_string(),
+                passed: false,
+                reason_code: Some("SIM_FAILED".to_string()),
+                details: Some("Custom(6005)".to_string()),
+            },
+        ],
+        "plan-hash-schema".to_string(),
+        SimulationResult {
+            success: false,
+            error_code: Some("Custom(6005)".to_string()),
+            logs_preview: Some("Program log: BondingCurveComplete".to_string()),
+            compute_units_consumed: Some(42_000),
+        },
+    );
+    record.header.ts_unix_ms = FIXED_TS_MS;
+    record
+}
+
+fn execution_result() -> ExecutionResult {
+    let mut result = ExecutionResult::new_sent(
+        "execution-engine",
+        "schema-fixture",
+        "run-schema",
+        "exec-schema-001".to_string(),
+        "dec-schema-001".to_string(),
+        "intent-schema-001".to_string(),
+        "momentum-bot".to_string(),
+        Some(MINT.to_string()),
+        Some(
+            "SchemaSig1111111111111111111111111111111111111111111111111111111111111111111111111111"
+                .to_string(),
+        ),
+        None,
+    )
+    .mark_confirmed(
+        300_000_010,
+        ExecutionFees {
+            network_fee_lamports: 5_000,
+            tip_lamports: 10_000,
+            compute_units: 120_000,
+        },
+        ExecutionPnl {
+            gross_lamports: 0,
+            net_lamports: -15_000,
+            decimals: 9,
+        },
+        420,
+    )
+    .with_fills(
+        Some(ExplicitAmount::new(50_000_000, 9)),
+        Some(ExplicitAmount::new(1_234_567_890, 6)),
+    );
+    result.header.ts_unix_ms = FIXED_TS_MS;
+    result
+}
+
+fn write_frozen<T: Serialize>(version: u32, type_name: &str, value: &T) {
+    let path = schema_dir(version).join(format!("{type_name}.json"));
+    if path.is_file() {
+        eprintln!("keep (frozen): {path:?}");
+        return;
+    }
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let json = serde_json::to_string_pretty(value).unwrap();
+    fs::write(&path, json + "\n").unwrap_or_else(|e| panic!("write {path:?}: {e}"));
+    eprintln!("captured: {path:?}");
+}
+
+/// Schreibt fehlende Fixtures fuer die `schema_version` des gepinnten Revs.
+#[test]
+#[ignore = "Capture-Tool: nur mit IRONCRAB_CAPTURE_SCHEMA_FIXTURES=1 und --ignored"]
+fn capture_schema_fixtures_for_pinned_rev() {
+    if std::env::var("IRONCRAB_CAPTURE_SCHEMA_FIXTURES").as_deref() != Ok("1") {
+        eprintln!("SKIP: IRONCRAB_CAPTURE_SCHEMA_FIXTURES=1 nicht gesetzt");
+        return;
+    }
+    let version = trade_intent().header.schema_version;
+    write_frozen(version, "market_event", &market_event());
+    write_frozen(version, "trade_intent", &trade_intent());
+    write_frozen(version, "decision_record", &decision_record());
+    write_frozen(version, "execution_result", &execution_result());
+}